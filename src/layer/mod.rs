@@ -0,0 +1,245 @@
+//! Layer shell surfaces in the Oxi theme.
+//!
+//! A bar along the top edge, `Bar` implements [`LayerApp`]:
+//! ```ignore
+//! layer("bar")
+//!     .anchor(Anchor::Top | Anchor::Left | Anchor::Right)
+//!     .size(0, 32)
+//!     .exclusive_zone(32)
+//!     .run::<Bar>()
+//!     .unwrap();
+//! ```
+use std::fmt::Debug;
+
+pub mod notification;
+pub mod osd;
+
+use iced::{Element, Subscription, Task, Theme};
+use iced_layershell::actions::LayershellCustomActions;
+use iced_layershell::settings::{LayerShellSettings, Settings, StartMode};
+
+pub use iced_layershell::reexport::{Anchor, KeyboardInteractivity, Layer};
+
+use crate::{theme::theme_impl::get_derived_iced_theme, widgets::oxi_layer};
+
+/// Corner or edge of the screen a template surface sits at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    Top,
+    TopLeft,
+    TopRight,
+    Bottom,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl Position {
+    pub fn anchor(self) -> Anchor {
+        match self {
+            Position::Top => Anchor::Top,
+            Position::TopLeft => Anchor::Top | Anchor::Left,
+            Position::TopRight => Anchor::Top | Anchor::Right,
+            Position::Bottom => Anchor::Bottom,
+            Position::BottomLeft => Anchor::Bottom | Anchor::Left,
+            Position::BottomRight => Anchor::Bottom | Anchor::Right,
+            Position::Center => Anchor::empty(),
+        }
+    }
+
+    /// Whether content stacks down from the top edge
+    pub fn is_top(self) -> bool {
+        matches!(self, Position::Top | Position::TopLeft | Position::TopRight)
+    }
+}
+
+/// Content of a layer surface, the surface itself is configured by [`LayerBuilder`]
+pub trait LayerApp: Sized + 'static {
+    type Message: TryInto<LayershellCustomActions, Error = Self::Message> + Debug + Send + 'static;
+
+    fn boot() -> (Self, Task<Self::Message>);
+
+    fn update(&mut self, message: Self::Message) -> Task<Self::Message>;
+
+    /// Content of the surface, drawn inside of a [`rounded_layer`](oxi_layer::rounded_layer)
+    fn view(&self) -> Element<'_, Self::Message>;
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        Subscription::none()
+    }
+}
+
+/// Configuration of a layer surface, see [`layer`]
+#[derive(Debug, Clone)]
+pub struct LayerBuilder {
+    namespace: String,
+    anchor: Anchor,
+    layer: Layer,
+    /// top, right, bottom, left as in the protocol
+    margin: (i32, i32, i32, i32),
+    exclusive_zone: i32,
+    keyboard_interactivity: KeyboardInteractivity,
+    size: Option<(u32, u32)>,
+    max_size: (u32, u32),
+    plain: bool,
+}
+
+/// Layer surface with the given namespace, which compositors use to tell surfaces apart.
+///
+/// Defaults to an unanchored surface on the top layer that takes no keyboard input.
+pub fn layer(namespace: impl Into<String>) -> LayerBuilder {
+    LayerBuilder {
+        namespace: namespace.into(),
+        anchor: Anchor::empty(),
+        layer: Layer::Top,
+        margin: (0, 0, 0, 0),
+        exclusive_zone: 0,
+        keyboard_interactivity: KeyboardInteractivity::None,
+        size: None,
+        max_size: (u32::MAX, u32::MAX),
+        plain: false,
+    }
+}
+
+impl LayerBuilder {
+    /// Edges the surface sticks to, anchoring opposite edges stretches the surface
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Distance to the anchored edges, margins of other edges are ignored by the compositor
+    pub fn margin(mut self, top: i32, right: i32, bottom: i32, left: i32) -> Self {
+        self.margin = (top, right, bottom, left);
+        self
+    }
+
+    /// Space reserved at the anchored edge, 0 avoids the zones of other surfaces
+    /// and -1 ignores them
+    pub fn exclusive_zone(mut self, exclusive_zone: i32) -> Self {
+        self.exclusive_zone = exclusive_zone;
+        self
+    }
+
+    pub fn keyboard_interactivity(mut self, interactivity: KeyboardInteractivity) -> Self {
+        self.keyboard_interactivity = interactivity;
+        self
+    }
+
+    /// Size of the surface, stretched axes ignore the given length
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Largest size of the rounded box around the content
+    pub fn max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = (width, height);
+        self
+    }
+
+    /// Draws the content without the rounded layer, for content that brings its own
+    pub fn plain(mut self) -> Self {
+        self.plain = true;
+        self
+    }
+
+    fn stretches(&self, first: Anchor, second: Anchor) -> bool {
+        self.anchor.contains(first) && self.anchor.contains(second)
+    }
+
+    /// Settings handed to iced_layershell.
+    /// Stretched axes get a size of 0, which tells the compositor to fill them.
+    pub fn settings(&self) -> Settings {
+        let size = self.size.map(|(width, height)| {
+            (
+                if self.stretches(Anchor::Left, Anchor::Right) {
+                    0
+                } else {
+                    width
+                },
+                if self.stretches(Anchor::Top, Anchor::Bottom) {
+                    0
+                } else {
+                    height
+                },
+            )
+        });
+        Settings {
+            id: Some(self.namespace.clone()),
+            layer_settings: LayerShellSettings {
+                anchor: self.anchor,
+                layer: self.layer,
+                exclusive_zone: self.exclusive_zone,
+                size,
+                margin: self.margin,
+                keyboard_interactivity: self.keyboard_interactivity,
+                start_mode: StartMode::Active,
+                ..LayerShellSettings::default()
+            },
+            ..Settings::default()
+        }
+    }
+
+    /// Runs the surface with the Oxi theme, the content is wrapped in a rounded layer
+    /// unless the surface is [`plain`](Self::plain)
+    pub fn run<A: LayerApp>(self) -> Result<(), iced_layershell::Error> {
+        let settings = self.settings();
+        let namespace = self.namespace;
+        let max_size = self.max_size;
+        let plain = self.plain;
+        iced_layershell::application(
+            A::boot,
+            move || namespace.clone(),
+            A::update,
+            move |app: &A| {
+                if plain {
+                    app.view()
+                } else {
+                    oxi_layer::rounded_layer(app.view(), max_size)
+                }
+            },
+        )
+        .settings(settings)
+        .theme(|_: &A| get_derived_iced_theme())
+        .style(|_: &A, _: &Theme| oxi_layer::layer_theme())
+        .subscription(A::subscription)
+        .run()
+    }
+}
+
+#[test]
+fn test_stretched_axes_have_no_size() {
+    let bar = layer("bar")
+        .anchor(Anchor::Top | Anchor::Left | Anchor::Right)
+        .size(800, 32)
+        .margin(4, 4, 0, 4)
+        .exclusive_zone(32)
+        .settings();
+    assert_eq!(bar.id.as_deref(), Some("bar"));
+    let bar = bar.layer_settings;
+    assert_eq!(bar.size, Some((0, 32)));
+    assert_eq!(bar.margin, (4, 4, 0, 4));
+    assert_eq!(bar.exclusive_zone, 32);
+    assert_eq!(bar.layer, Layer::Top);
+    assert_eq!(bar.keyboard_interactivity, KeyboardInteractivity::None);
+
+    let popup = layer("popup")
+        .size(300, 200)
+        .layer(Layer::Overlay)
+        .keyboard_interactivity(KeyboardInteractivity::OnDemand)
+        .settings()
+        .layer_settings;
+    assert_eq!(popup.size, Some((300, 200)));
+    assert_eq!(popup.anchor, Anchor::empty());
+    assert_eq!(popup.layer, Layer::Overlay);
+    assert_eq!(
+        popup.keyboard_interactivity,
+        KeyboardInteractivity::OnDemand
+    );
+}
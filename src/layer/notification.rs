@@ -0,0 +1,414 @@
+//! Stack of notification bubbles.
+use std::time::Duration;
+
+use iced::advanced::text::{self as core_text, Paragraph};
+use iced::alignment::Vertical;
+use iced::time::Instant;
+use iced::widget::svg::Handle;
+use iced::widget::text::{LineHeight, Shaping, Wrapping};
+use iced::widget::{Column, column, container, mouse_area, row, text};
+use iced::{Alignment, Element, Font, Length, Size, Subscription, Task, window};
+use lilt::Animated;
+
+use crate::{
+    layer::{KeyboardInteractivity, Layer, LayerBuilder, Position, layer},
+    theme::theme_impl::{Motion, OXITHEME},
+    widgets::{common::Variant, oxi_icon::Icon, oxi_layer::faded_layer, oxi_svg::svg_from_handle},
+};
+
+const NOTIFICATION_WIDTH: u32 = 400;
+/// Distance to the screen edges
+const NOTIFICATION_MARGIN: i32 = 16;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_VISIBLE: usize = 3;
+/// How often timeouts are checked while nothing animates
+const IDLE_TICK: Duration = Duration::from_millis(250);
+/// Long words break anywhere, so the measured height is the drawn one
+const WRAPPING: Wrapping = Wrapping::WordOrGlyph;
+
+/// Identifies a notification in its [`Notifications`] stack
+pub type NotificationId = u64;
+
+/// Content of a notification bubble
+#[derive(Debug, Clone)]
+pub struct Notification {
+    summary: String,
+    body: Option<String>,
+    icon: Option<Handle>,
+    /// None uses the timeout of the stack, Some(None) never expires
+    timeout: Option<Option<Duration>>,
+}
+
+impl Notification {
+    pub fn new(summary: impl Into<String>) -> Self {
+        Self {
+            summary: summary.into(),
+            body: None,
+            icon: None,
+            timeout: None,
+        }
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    pub fn icon(mut self, icon: impl Icon) -> Self {
+        self.icon = Some(icon.handle(OXITHEME.icon_size));
+        self
+    }
+
+    /// Overrides the timeout of the stack
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(Some(timeout));
+        self
+    }
+
+    /// Stays until it is dismissed
+    pub fn sticky(mut self) -> Self {
+        self.timeout = Some(None);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    id: NotificationId,
+    notification: Notification,
+    /// Notifications beyond the visible limit wait until there is room
+    shown: bool,
+    expires_at: Option<Instant>,
+    visibility: Animated<bool, Instant>,
+}
+
+impl Entry {
+    fn is_visible(&self, now: Instant) -> bool {
+        self.visibility.value || self.visibility.in_progress(now)
+    }
+
+    /// Height of the bubble, the text is laid out like [`Notifications::bubble`] does
+    fn height(&self, font: Font) -> f32 {
+        let notification = &self.notification;
+        let mut text_width = NOTIFICATION_WIDTH as f32 - 4.0 * OXITHEME.padding_xl;
+        if notification.icon.is_some() {
+            text_width -= OXITHEME.icon_size as f32 + OXITHEME.padding_lg;
+        }
+        let summary = text_height(&notification.summary, OXITHEME.font_lg, text_width, font);
+        let body = notification.body.as_ref().map_or(0.0, |body| {
+            OXITHEME.padding_xs + text_height(body, OXITHEME.font_md, text_width, font)
+        });
+        (summary + body).max(OXITHEME.icon_size as f32) + 4.0 * OXITHEME.padding_xl
+    }
+}
+
+/// Height of the text wrapped at `width`
+fn text_height(content: &str, size: f32, width: f32, font: Font) -> f32 {
+    <iced::Renderer as core_text::Renderer>::Paragraph::with_text(core_text::Text {
+        content,
+        bounds: Size::new(width, f32::INFINITY),
+        size: size.into(),
+        line_height: LineHeight::default(),
+        font,
+        align_x: core_text::Alignment::Default,
+        align_y: Vertical::Top,
+        shaping: Shaping::default(),
+        wrapping: WRAPPING,
+    })
+    .min_height()
+}
+
+/// Notification bubbles that fade in, stack up at a screen position and fade out
+/// after their timeout or when clicked.
+///
+/// Feed [`subscription`](Notifications::subscription) into [`tick`](Notifications::tick).
+#[derive(Debug)]
+pub struct Notifications {
+    entries: Vec<Entry>,
+    position: Position,
+    timeout: Duration,
+    max_visible: usize,
+    font: Font,
+    next_id: NotificationId,
+    now: Instant,
+}
+
+impl Notifications {
+    pub fn new(position: Position) -> Self {
+        Self {
+            entries: Vec::new(),
+            position,
+            timeout: DEFAULT_TIMEOUT,
+            max_visible: DEFAULT_MAX_VISIBLE,
+            font: Font::DEFAULT,
+            next_id: 0,
+            now: Instant::now(),
+        }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Bubbles shown at once, newer ones wait until older ones are gone
+    pub fn max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible.max(1);
+        self
+    }
+
+    /// Font of the bubbles, needed to size the surface when the app has another default font
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    pub fn push(&mut self, notification: Notification, now: Instant) -> NotificationId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push(Entry {
+            id,
+            notification,
+            shown: false,
+            expires_at: None,
+            visibility: OXITHEME.animated(false, Motion::Transition),
+        });
+        self.tick(now);
+        id
+    }
+
+    /// Fades the notification out
+    pub fn dismiss(&mut self, id: NotificationId, now: Instant) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            if entry.visibility.value {
+                entry.visibility.transition(false, now);
+            } else if !entry.shown {
+                // never shown, nothing to fade
+                entry.shown = true;
+            }
+            entry.expires_at = None;
+        }
+        self.tick(now);
+    }
+
+    /// Expires notifications, drops faded out ones and shows waiting ones
+    pub fn tick(&mut self, now: Instant) {
+        self.now = now;
+        for entry in &mut self.entries {
+            if entry.expires_at.is_some_and(|expires_at| expires_at <= now) {
+                entry.expires_at = None;
+                entry.visibility.transition(false, now);
+            }
+        }
+        self.entries
+            .retain(|entry| !entry.shown || entry.is_visible(now));
+
+        let mut room = self
+            .max_visible
+            .saturating_sub(self.entries.iter().filter(|entry| entry.shown).count());
+        for entry in self.entries.iter_mut().filter(|entry| !entry.shown) {
+            if room == 0 {
+                break;
+            }
+            room -= 1;
+            entry.shown = true;
+            entry.expires_at = entry
+                .notification
+                .timeout
+                .unwrap_or(Some(self.timeout))
+                .map(|timeout| now + timeout);
+            entry.visibility.transition(true, now);
+        }
+    }
+
+    /// No notification is shown or waiting, the surface can be closed
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Exits the surface once the last notification faded out, return it from `update`
+    /// after [`tick`](Self::tick) and [`dismiss`](Self::dismiss)
+    pub fn close_when_empty<M: Send + 'static>(&self) -> Task<M> {
+        if self.is_empty() {
+            iced::exit()
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Size of the surface that fits the shown bubbles, so the rest of the screen
+    /// stays clickable. Send it as a size change whenever it differs from the last one.
+    pub fn size(&self) -> (u32, u32) {
+        let height = self
+            .entries
+            .iter()
+            .filter(|entry| entry.shown)
+            .map(|entry| entry.height(self.font) + OXITHEME.padding_md)
+            .sum::<f32>();
+        // the protocol does not allow an empty surface
+        (NOTIFICATION_WIDTH, (height.ceil() as u32).max(1))
+    }
+
+    /// Every frame while a bubble fades, otherwise often enough to catch timeouts
+    pub fn subscription(&self) -> Subscription<Instant> {
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.visibility.in_progress(self.now))
+        {
+            window::frames()
+        } else if self.is_empty() {
+            Subscription::none()
+        } else {
+            iced::time::every(IDLE_TICK)
+        }
+    }
+
+    /// The stack, newest bubbles are closest to the screen edge.
+    /// Clicking a bubble produces `on_dismiss`.
+    pub fn view<'a, M: Clone + 'a>(
+        &self,
+        on_dismiss: impl Fn(NotificationId) -> M,
+    ) -> Element<'a, M> {
+        let bubbles = self
+            .entries
+            .iter()
+            .filter(|entry| entry.shown)
+            .map(|entry| {
+                mouse_area(self.bubble(entry))
+                    .on_press(on_dismiss(entry.id))
+                    .into()
+            });
+        let stack: Column<'a, M> = if self.position.is_top() {
+            Column::from_iter(bubbles.rev())
+        } else {
+            Column::from_iter(bubbles)
+        };
+        container(stack.spacing(OXITHEME.padding_md))
+            .height(Length::Fill)
+            .align_y(if self.position.is_top() {
+                Alignment::Start
+            } else {
+                Alignment::End
+            })
+            .into()
+    }
+
+    fn bubble<'a, M: 'a>(&self, entry: &Entry) -> Element<'a, M> {
+        let opacity = entry.visibility.animate_bool(0.0, 1.0, self.now);
+        let notification = &entry.notification;
+        let icon = notification.icon.clone().map(|icon| {
+            svg_from_handle(Variant::Primary, icon)
+                .width(OXITHEME.icon_size as f32)
+                .height(OXITHEME.icon_size as f32)
+                .opacity(opacity)
+        });
+        let body = notification.body.clone().map(|body| {
+            text(body)
+                .size(OXITHEME.font_md)
+                .font(self.font)
+                .wrapping(WRAPPING)
+                .color(OXITHEME.text.scale_alpha(opacity))
+        });
+        let content = row![]
+            .push(icon)
+            .push(
+                column![
+                    text(notification.summary.clone())
+                        .size(OXITHEME.font_lg)
+                        .font(self.font)
+                        .wrapping(WRAPPING)
+                        .color(OXITHEME.text.scale_alpha(opacity))
+                ]
+                .push(body)
+                .spacing(OXITHEME.padding_xs),
+            )
+            .spacing(OXITHEME.padding_lg);
+        faded_layer(content, (NOTIFICATION_WIDTH, u32::MAX), opacity)
+    }
+
+    /// Overlay surface at the position of the stack with the current [`size`](Self::size)
+    pub fn layer(&self, namespace: impl Into<String>) -> LayerBuilder {
+        let (width, height) = self.size();
+        layer(namespace)
+            .anchor(self.position.anchor())
+            .layer(Layer::Overlay)
+            .keyboard_interactivity(KeyboardInteractivity::None)
+            .margin(
+                NOTIFICATION_MARGIN,
+                NOTIFICATION_MARGIN,
+                NOTIFICATION_MARGIN,
+                NOTIFICATION_MARGIN,
+            )
+            .size(width, height)
+            .plain()
+    }
+}
+
+#[test]
+fn test_notifications_stack_and_expire() {
+    let start = Instant::now();
+    let mut notifications = Notifications::new(Position::TopRight)
+        .timeout(Duration::from_secs(1))
+        .max_visible(2);
+    let first = notifications.push(Notification::new("first"), start);
+    notifications.push(Notification::new("second").sticky(), start);
+    let third = notifications.push(Notification::new("third"), start);
+    let shown = |notifications: &Notifications| {
+        notifications
+            .entries
+            .iter()
+            .filter(|entry| entry.shown)
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(shown(&notifications), vec![0, 1]);
+
+    // the first one expires and makes room for the third once it faded out
+    notifications.tick(start + Duration::from_secs(2));
+    notifications.tick(start + Duration::from_secs(5));
+    assert_eq!(shown(&notifications), vec![1, third]);
+
+    notifications.dismiss(third, start + Duration::from_secs(6));
+    notifications.tick(start + Duration::from_secs(10));
+    assert_eq!(shown(&notifications), vec![1]);
+    assert!(!notifications.entries.iter().any(|entry| entry.id == first));
+    assert!(!notifications.is_empty());
+}
+
+#[test]
+fn test_surface_fits_the_shown_bubbles() {
+    let start = Instant::now();
+    let mut notifications = Notifications::new(Position::BottomRight).max_visible(1);
+    assert_eq!(notifications.size().1, 1);
+
+    notifications.push(Notification::new("short").body("one line"), start);
+    let (width, short) = notifications.size();
+    assert_eq!(width, NOTIFICATION_WIDTH);
+    assert!(short > 1);
+
+    // the waiting notification takes no room until it is shown
+    notifications.push(Notification::new("long").body("word ".repeat(60)), start);
+    assert_eq!(notifications.size().1, short);
+    notifications.dismiss(0, start);
+    notifications.tick(start + Duration::from_secs(2));
+    assert!(notifications.size().1 > short + 2 * OXITHEME.font_md as u32);
+}
+
+#[test]
+fn test_wide_glyphs_and_long_words_wrap() {
+    let height = |body: String| {
+        Entry {
+            id: 0,
+            notification: Notification::new("summary").body(body),
+            shown: true,
+            expires_at: None,
+            visibility: Animated::new(true),
+        }
+        .height(Font::DEFAULT)
+    };
+    let one_line = height(String::from("a"));
+    // 40 wide glyphs or one long word do not fit in one line
+    assert!(height("通知".repeat(20)) > one_line + OXITHEME.font_md);
+    assert!(height("a".repeat(200)) > one_line + OXITHEME.font_md);
+}
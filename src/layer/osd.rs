@@ -0,0 +1,182 @@
+//! On-screen display for values like volume and brightness.
+use std::time::Duration;
+
+use iced::time::Instant;
+use iced::widget::svg::Handle;
+use iced::widget::{progress_bar::Style, row};
+use iced::{Alignment, Element, Subscription, Task, Theme, window};
+use lilt::Animated;
+
+use crate::{
+    layer::{KeyboardInteractivity, Layer, LayerBuilder, Position, layer},
+    theme::theme_impl::{Motion, OXITHEME},
+    widgets::{
+        common::Variant,
+        oxi_icon::Icon,
+        oxi_layer::faded_layer,
+        oxi_progress::{progress_bar, progress_variant_style},
+        oxi_svg::svg_from_handle,
+    },
+};
+
+const OSD_WIDTH: u32 = 320;
+/// Distance to the screen edge
+const OSD_MARGIN: i32 = 48;
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1500);
+/// How often the timeout is checked while nothing animates
+const IDLE_TICK: Duration = Duration::from_millis(100);
+
+/// Icon and bar that fade in when a value changes and fade out after a timeout.
+///
+/// Feed [`subscription`](Osd::subscription) into [`tick`](Osd::tick).
+#[derive(Debug)]
+pub struct Osd {
+    icon: Option<Handle>,
+    value: f32,
+    variant: Variant,
+    position: Position,
+    timeout: Duration,
+    visibility: Animated<bool, Instant>,
+    hide_at: Option<Instant>,
+    now: Instant,
+}
+
+impl Osd {
+    pub fn new(position: Position) -> Self {
+        Self {
+            icon: None,
+            value: 0.0,
+            variant: Variant::Primary,
+            position,
+            timeout: DEFAULT_TIMEOUT,
+            visibility: OXITHEME.animated(false, Motion::Transition),
+            hide_at: None,
+            now: Instant::now(),
+        }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Shows a value between 0 and 1, every call restarts the timeout
+    pub fn show(&mut self, icon: impl Icon, value: f32, now: Instant) {
+        self.icon = Some(icon.handle(OXITHEME.icon_size));
+        self.value = value.clamp(0.0, 1.0);
+        self.hide_at = Some(now + self.timeout);
+        self.now = now;
+        if !self.visibility.value {
+            self.visibility.transition(true, now);
+        }
+    }
+
+    pub fn hide(&mut self, now: Instant) {
+        self.hide_at = None;
+        self.now = now;
+        if self.visibility.value {
+            self.visibility.transition(false, now);
+        }
+    }
+
+    pub fn tick(&mut self, now: Instant) {
+        self.now = now;
+        if self.hide_at.is_some_and(|hide_at| hide_at <= now) {
+            self.hide(now);
+        }
+    }
+
+    /// Shown or still fading out, the surface can be closed once this is false
+    pub fn is_visible(&self) -> bool {
+        self.visibility.value || self.visibility.in_progress(self.now)
+    }
+
+    /// Exits the surface once the display faded out, return it from `update` after
+    /// [`tick`](Self::tick) and [`hide`](Self::hide)
+    pub fn close_when_hidden<M: Send + 'static>(&self) -> Task<M> {
+        if self.is_visible() {
+            Task::none()
+        } else {
+            iced::exit()
+        }
+    }
+
+    /// Every frame while fading, otherwise often enough to catch the timeout
+    pub fn subscription(&self) -> Subscription<Instant> {
+        if self.visibility.in_progress(self.now) {
+            window::frames()
+        } else if self.visibility.value {
+            iced::time::every(IDLE_TICK)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    pub fn view<'a, M: 'a>(&self) -> Element<'a, M> {
+        let opacity = self.visibility.animate_bool(0.0, 1.0, self.now);
+        let variant = self.variant;
+        let icon = self.icon.clone().map(|icon| {
+            svg_from_handle(variant, icon)
+                .width(OXITHEME.icon_size as f32)
+                .height(OXITHEME.icon_size as f32)
+                .opacity(opacity)
+        });
+        let bar = progress_bar(0.0..=1.0, self.value).style(move |theme: &Theme| {
+            let style = progress_variant_style(variant, theme);
+            Style {
+                background: style.background.scale_alpha(opacity),
+                bar: style.bar.scale_alpha(opacity),
+                border: style.border.color(style.border.color.scale_alpha(opacity)),
+            }
+        });
+        let content = row![]
+            .push(icon)
+            .push(bar)
+            .spacing(OXITHEME.padding_lg)
+            .align_y(Alignment::Center);
+        faded_layer(content, (OSD_WIDTH, u32::MAX), opacity)
+    }
+
+    /// Overlay surface at the position of the display that ignores other exclusive zones
+    pub fn layer(&self, namespace: impl Into<String>) -> LayerBuilder {
+        let height = OXITHEME.icon_size + 4 * OXITHEME.padding_xl as u32;
+        layer(namespace)
+            .anchor(self.position.anchor())
+            .layer(Layer::Overlay)
+            .keyboard_interactivity(KeyboardInteractivity::None)
+            .exclusive_zone(-1)
+            .margin(OSD_MARGIN, OSD_MARGIN, OSD_MARGIN, OSD_MARGIN)
+            .size(OSD_WIDTH, height)
+            .plain()
+    }
+}
+
+#[test]
+fn test_osd_hides_after_timeout() {
+    let start = Instant::now();
+    let mut osd = Osd::new(Position::Bottom).timeout(Duration::from_secs(1));
+    assert!(!osd.is_visible());
+
+    osd.show("audio-volume-high-symbolic", 1.5, start);
+    assert!(osd.is_visible());
+    assert_eq!(osd.value, 1.0);
+
+    // showing again restarts the timeout
+    osd.show(
+        "audio-volume-high-symbolic",
+        0.5,
+        start + Duration::from_millis(800),
+    );
+    osd.tick(start + Duration::from_millis(1200));
+    assert!(osd.visibility.value);
+
+    osd.tick(start + Duration::from_millis(1900));
+    assert!(!osd.visibility.value);
+    osd.tick(start + Duration::from_secs(10));
+    assert!(!osd.is_visible());
+}
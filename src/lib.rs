@@ -1,4 +1,5 @@
 pub mod any_send;
+pub mod layer;
 pub mod theme;
 pub mod utils;
 pub mod widgets;
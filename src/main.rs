@@ -1,6 +1,8 @@
 use std::env;
 
-use crate::tools::{palette::palette, test_app::test_app};
+use crate::tools::{
+    import::import, migrate::migrate, palette::palette, test_app::test_app, wallpaper::wallpaper,
+};
 
 pub mod theme;
 mod tools;
@@ -11,7 +13,10 @@ pub fn main() -> Result<(), iced::Error> {
     let args: Vec<String> = env::args().collect();
     let tool = args.get(1).unwrap_or(&String::from("test_app")).clone();
     match tool.as_str() {
+        "import" => import(),
+        "migrate" => migrate(),
         "palette" => palette(),
+        "wallpaper" => wallpaper(),
         _ => test_app(),
     }
 }
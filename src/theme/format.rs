@@ -0,0 +1,228 @@
+use std::fmt;
+
+use optional_struct::Applicable;
+use serde::Deserialize;
+
+use crate::{
+    theme::{
+        import::ImportError,
+        legacy_theme::{TomlBase16, TomlExtended, TomlSimple},
+        migration::{DEPRECATED_KEYS, MigrationError, migrate},
+        theme_impl::{OptionalOxiTheme, OxiTheme, default_theme},
+    },
+    utils::file::get_theme_toml,
+};
+
+/// Value of the optional `format` key in theme.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeFormat {
+    Oxi,
+    Base16,
+    Simple,
+    Extended,
+}
+
+impl ThemeFormat {
+    /// Order in which formats are detected, oxi accepts partial themes and therefore comes last
+    pub const DETECTION_ORDER: [ThemeFormat; 4] = [
+        ThemeFormat::Base16,
+        ThemeFormat::Simple,
+        ThemeFormat::Extended,
+        ThemeFormat::Oxi,
+    ];
+
+    /// Every format is converted into an [`OxiTheme`], iced themes are derived from that
+    pub fn parse(&self, theme_string: &str) -> Result<OxiTheme, FormatError> {
+        match self {
+            ThemeFormat::Oxi => parse_oxi(theme_string),
+            ThemeFormat::Base16 => Ok(toml::from_str::<TomlBase16>(theme_string)?.into()),
+            ThemeFormat::Simple => Ok(toml::from_str::<TomlSimple>(theme_string)?.into()),
+            ThemeFormat::Extended => Ok(toml::from_str::<TomlExtended>(theme_string)?.into()),
+        }
+    }
+}
+
+impl fmt::Display for ThemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ThemeFormat::Oxi => "oxi",
+            ThemeFormat::Base16 => "base16",
+            ThemeFormat::Simple => "simple",
+            ThemeFormat::Extended => "extended",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a theme could not be parsed in one format
+#[derive(Debug)]
+pub enum FormatError {
+    Toml(toml::de::Error),
+    /// Every table parses as a partial oxi theme, so at least one of its keys has to be set
+    NoOxiKeys,
+    /// The `[import]` source could not be read
+    Import(ImportError),
+    Migration(MigrationError),
+}
+
+impl From<toml::de::Error> for FormatError {
+    fn from(value: toml::de::Error) -> Self {
+        FormatError::Toml(value)
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Toml(error) => f.write_str(error.message()),
+            FormatError::NoOxiKeys => f.write_str("no oxi theme keys found"),
+            FormatError::Import(error) => write!(f, "{error}"),
+            FormatError::Migration(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    /// theme.toml exists but could not be read
+    Io(std::io::Error),
+    /// theme.toml is not valid toml or declares an unknown format
+    Syntax(toml::de::Error),
+    /// The declared format did not parse
+    Format {
+        format: ThemeFormat,
+        error: FormatError,
+    },
+    /// No format was declared and none of them parsed
+    Detection(DetectionReport),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(error) => write!(f, "could not read theme: {error}"),
+            ThemeError::Syntax(error) => write!(f, "invalid theme: {error}"),
+            ThemeError::Format { format, error } => {
+                write!(f, "theme declared as {format} is invalid: {error}")
+            }
+            ThemeError::Detection(report) => write!(f, "{report}"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// Every format that was tried without an explicit `format` key, in order
+#[derive(Debug, Default)]
+pub struct DetectionReport {
+    pub attempts: Vec<(ThemeFormat, FormatError)>,
+}
+
+impl fmt::Display for DetectionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "theme format could not be detected, set `format` to skip detection"
+        )?;
+        for (format, error) in &self.attempts {
+            write!(f, "\n  {format}: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct FormatKey {
+    format: Option<ThemeFormat>,
+}
+
+/// Loads theme.toml from the user config, without one the default theme is used
+pub fn load_theme() -> Result<OxiTheme, ThemeError> {
+    match get_theme_toml() {
+        Ok(theme_string) => parse_theme(&theme_string),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(default_theme()),
+        Err(error) => Err(ThemeError::Io(error)),
+    }
+}
+
+/// Parses a theme in its declared format, or detects the format if none is declared
+pub fn parse_theme(theme_string: &str) -> Result<OxiTheme, ThemeError> {
+    parse_detected(theme_string).map(|(_, theme)| theme)
+}
+
+/// The declared format of a theme, or the first format it parses in
+pub fn detect_format(theme_string: &str) -> Result<ThemeFormat, ThemeError> {
+    parse_detected(theme_string).map(|(format, _)| format)
+}
+
+fn parse_detected(theme_string: &str) -> Result<(ThemeFormat, OxiTheme), ThemeError> {
+    let declared = toml::from_str::<FormatKey>(theme_string).map_err(ThemeError::Syntax)?;
+    if let Some(format) = declared.format {
+        return format
+            .parse(theme_string)
+            .map(|theme| (format, theme))
+            .map_err(|error| ThemeError::Format { format, error });
+    }
+    let mut report = DetectionReport::default();
+    for format in ThemeFormat::DETECTION_ORDER {
+        match format.parse(theme_string) {
+            Ok(theme) => return Ok((format, theme)),
+            Err(error) => report.attempts.push((format, error)),
+        }
+    }
+    Err(ThemeError::Detection(report))
+}
+
+fn parse_oxi(theme_string: &str) -> Result<OxiTheme, FormatError> {
+    let table: toml::Table = toml::from_str(theme_string)?;
+    let oxi_keys = toml::Table::try_from(default_theme()).expect("theme can always be serialized");
+    // unset options such as import are not serialized, name and version exist in other formats too
+    let is_oxi_key = |key: &String| {
+        (oxi_keys.contains_key(key) && key != "name" && key != "version")
+            || key == "import"
+            || DEPRECATED_KEYS.contains(&key.as_str())
+    };
+    if !table.keys().any(is_oxi_key) {
+        return Err(FormatError::NoOxiKeys);
+    }
+    let migrated = migrate(table).map_err(FormatError::Migration)?;
+    for deprecation in &migrated.deprecations {
+        eprintln!("warning: {deprecation}, run `oxiced migrate` to update theme.toml");
+    }
+    let theme: OptionalOxiTheme = migrated.table.try_into()?;
+    // explicit keys in theme.toml win over imported colors
+    let base = match theme.import.clone().flatten() {
+        Some(config) => config.import().map_err(FormatError::Import)?,
+        None => default_theme(),
+    };
+    Ok(theme.build(base))
+}
+
+#[test]
+fn test_declared_format_reports_its_error() {
+    let error = parse_theme("format = \"base16\"\nname = \"broken\"\nbase00 = \"000000\"");
+    assert!(matches!(
+        error,
+        Err(ThemeError::Format {
+            format: ThemeFormat::Base16,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_detection_reports_every_format() {
+    let Err(ThemeError::Detection(report)) = parse_theme("nmae = \"typo\"") else {
+        panic!("theme without known keys must not be detected");
+    };
+    let tried: Vec<ThemeFormat> = report.attempts.iter().map(|(format, _)| *format).collect();
+    assert_eq!(tried, ThemeFormat::DETECTION_ORDER);
+}
+
+#[test]
+fn test_shared_keys_do_not_make_an_oxi_theme() {
+    // name and version are also keys of the other formats
+    let report = parse_theme("name = \"typo\"\nversion = 3\nbase0 = \"000000\"");
+    assert!(matches!(report, Err(ThemeError::Detection(_))));
+}
@@ -0,0 +1,401 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use iced::{Color, Subscription, time::Instant};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    theme::{seed::contrast_hex, theme_impl::OxiTheme},
+    utils::{
+        color::{ensure_contrast, mix_oklab, mk_color, to_hex},
+        file::open_file,
+    },
+};
+
+/// Muted text still has to be readable, just less prominent
+const MUTED_TEXT_CONTRAST: f32 = 4.5;
+/// How often a watched source file is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tools whose color output can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorSource {
+    /// `~/.cache/wal/colors.json`
+    Pywal,
+    /// Output of the pywal compatible colors.json template
+    Wallust,
+    /// Output of `matugen --json hex`
+    Matugen,
+}
+
+impl ColorSource {
+    /// Where the tool writes its colors by default, wallust and matugen only write through
+    /// templates and have no default location.
+    pub fn default_path(&self) -> Option<PathBuf> {
+        match self {
+            ColorSource::Pywal => {
+                xdg::BaseDirectories::with_prefix("wal").get_cache_file("colors.json")
+            }
+            ColorSource::Wallust | ColorSource::Matugen => None,
+        }
+    }
+}
+
+/// `[import]` table in theme.toml.
+///
+/// The colors are imported whenever the theme is loaded, with `watch = true` apps can
+/// follow changes of the source file through [`ImportConfig::watcher`].
+/// Keys set in theme.toml itself still take precedence over the imported colors.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImportConfig {
+    pub source: ColorSource,
+    /// Defaults to [`ColorSource::default_path`]
+    pub path: Option<PathBuf>,
+    /// Picks the matugen variant, dark by default.
+    /// pywal and wallust only contain one variant.
+    pub dark: Option<bool>,
+    /// Re-imports the colors when the source file changes, off by default
+    pub watch: Option<bool>,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    NoPath(ColorSource),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingColor(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::NoPath(source) => write!(f, "no path given for {source:?}"),
+            ImportError::Io(error) => write!(f, "could not read colors: {error}"),
+            ImportError::Json(error) => write!(f, "invalid colors: {error}"),
+            ImportError::MissingColor(name) => write!(f, "color {name} is missing"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl ImportConfig {
+    fn source_path(&self) -> Result<PathBuf, ImportError> {
+        self.path
+            .clone()
+            .or_else(|| self.source.default_path())
+            .ok_or(ImportError::NoPath(self.source))
+    }
+
+    /// None unless `watch` is set and the source has a path
+    pub fn watcher(&self) -> Option<ImportWatcher> {
+        if !self.watch.unwrap_or(false) {
+            return None;
+        }
+        let path = self.source_path().ok()?;
+        Some(ImportWatcher {
+            modified: modified(&path),
+            path,
+        })
+    }
+
+    pub fn import(&self) -> Result<OxiTheme, ImportError> {
+        let json = open_file(self.source_path()?).map_err(ImportError::Io)?;
+        match self.source {
+            ColorSource::Pywal | ColorSource::Wallust => {
+                let colors = serde_json::from_str(&json).map_err(ImportError::Json)?;
+                from_pywal(&colors)
+            }
+            ColorSource::Matugen => {
+                let colors = serde_json::from_str(&json).map_err(ImportError::Json)?;
+                from_matugen(&colors, self.dark.unwrap_or(true))
+            }
+        }
+    }
+}
+
+/// Watches the source file of an `[import]` with `watch = true`.
+///
+/// Feed [`subscription`](ImportWatcher::subscription) into [`check`](ImportWatcher::check)
+/// and call [`reload_theme`](crate::theme::theme_impl::reload_theme) when it reports a change:
+/// ```ignore
+/// Message::CheckImport(_) if watcher.check() => {
+///     self.theme = reload_theme().unwrap_or(self.theme.clone());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ImportWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ImportWatcher {
+    /// Polls the modification time of the source file
+    pub fn subscription(&self) -> Subscription<Instant> {
+        iced::time::every(WATCH_INTERVAL)
+    }
+
+    /// Whether the source file changed since the last check, tools rewrite it in place
+    /// or replace it, both change the modification time
+    pub fn check(&mut self) -> bool {
+        let modified = modified(&self.path);
+        let changed = modified.is_some() && modified != self.modified;
+        self.modified = modified;
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// pywal layout, wallust uses the same one in its colors.json template.
+/// Some templates place the special colors at the top level.
+#[derive(Deserialize)]
+struct PywalColors {
+    special: Option<HashMap<String, String>>,
+    colors: HashMap<String, String>,
+    background: Option<String>,
+    foreground: Option<String>,
+}
+
+impl PywalColors {
+    fn color(&self, name: &str) -> Result<Color, ImportError> {
+        let hex = self
+            .colors
+            .get(name)
+            .or_else(|| self.special.as_ref().and_then(|special| special.get(name)))
+            .or(match name {
+                "background" => self.background.as_ref(),
+                "foreground" => self.foreground.as_ref(),
+                _ => None,
+            })
+            .ok_or_else(|| ImportError::MissingColor(String::from(name)))?;
+        Ok(parse_hex(hex))
+    }
+}
+
+fn parse_hex(hex: &str) -> Color {
+    mk_color(hex.trim_start_matches('#'))
+}
+
+/// Maps the 16 terminal colors onto the theme.
+///
+/// color0–7 are the normal and color8–15 the bright terminal colors:
+/// black, red, green, yellow, blue, magenta, cyan, white.
+/// The pastel accents have no terminal color of their own, flamingo is a paler rose
+/// and lavender sits between blue and mauve.
+fn from_pywal(colors: &PywalColors) -> Result<OxiTheme, ImportError> {
+    let background = colors.color("background")?;
+    let foreground = colors.color("foreground")?;
+    let hex = |name: &str| colors.color(name).map(|color| to_hex(&color));
+    let contrast = |name: &str| colors.color(name).map(|color| contrast_hex(&color));
+    let muted = ensure_contrast(&colors.color("color8")?, &background, MUTED_TEXT_CONTRAST);
+    let between = |first: &str, second: &str| -> Result<String, ImportError> {
+        let mixed = mix_oklab(&colors.color(first)?, &colors.color(second)?, 0.5);
+        Ok(to_hex(&mixed))
+    };
+
+    Ok(OxiTheme {
+        base: hex("color0")?,
+        text_muted: to_hex(&muted),
+        primary: hex("color4")?,
+        primary_contrast: contrast("color4")?,
+        secondary: hex("color5")?,
+        secondary_contrast: contrast("color5")?,
+        good: hex("color2")?,
+        good_contrast: contrast("color2")?,
+        bad: hex("color1")?,
+        bad_contrast: contrast("color1")?,
+        warning: hex("color3")?,
+        warning_contrast: contrast("color3")?,
+        info: hex("color6")?,
+        info_contrast: contrast("color6")?,
+        rose: hex("color9")?,
+        flamingo: between("color9", "color15")?,
+        blue: hex("color12")?,
+        mauve: hex("color13")?,
+        lavender: between("color12", "color13")?,
+        border_color_strong: hex("color4")?,
+        ..OxiTheme::from_background(background, foreground, colors.color("color4")?)
+    })
+}
+
+/// matugen up to 2.3 groups by variant, later versions group by color
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MatugenColors {
+    ByVariant {
+        dark: HashMap<String, String>,
+        light: HashMap<String, String>,
+    },
+    ByColor(HashMap<String, HashMap<String, String>>),
+}
+
+#[derive(Deserialize)]
+struct MatugenOutput {
+    colors: MatugenColors,
+}
+
+/// Maps the Material 3 roles onto the theme, matugen has no success or warning roles,
+/// those are derived from primary like [`OxiTheme::from_seed`] does.
+fn from_matugen(output: &MatugenOutput, dark: bool) -> Result<OxiTheme, ImportError> {
+    let variant = if dark { "dark" } else { "light" };
+    let role = |name: &str| -> Result<String, ImportError> {
+        let hex = match &output.colors {
+            MatugenColors::ByVariant {
+                dark: dark_roles,
+                light,
+            } => {
+                if dark {
+                    dark_roles.get(name)
+                } else {
+                    light.get(name)
+                }
+            }
+            MatugenColors::ByColor(colors) => colors.get(name).and_then(|color| color.get(variant)),
+        };
+        hex.map(|hex| to_hex(&parse_hex(hex)))
+            .ok_or_else(|| ImportError::MissingColor(String::from(name)))
+    };
+    let primary = role("primary")?;
+    let seed = OxiTheme::from_seed(mk_color(&primary), dark);
+    // surface containers only exist since matugen 2.0
+    let container = |name: &str, fallback: &str| role(name).or_else(|_| role(fallback));
+
+    Ok(OxiTheme {
+        base: container("surface_container", "surface_variant")?,
+        mantle: role("surface")?,
+        primary_bg: container("surface_container_low", "surface")?,
+        secondary_bg: container("surface_container", "surface_variant")?,
+        tertiary_bg: container("surface_container_highest", "outline_variant")?,
+        text: role("on_surface")?,
+        text_muted: role("on_surface_variant")?,
+        primary_contrast: role("on_primary")?,
+        secondary: role("secondary")?,
+        secondary_contrast: role("on_secondary")?,
+        bad: role("error")?,
+        bad_contrast: role("on_error")?,
+        info: role("tertiary")?,
+        info_contrast: role("on_tertiary")?,
+        border_color_weak: role("outline_variant")?,
+        border_color_strong: primary.clone(),
+        primary,
+        ..seed
+    })
+}
+
+#[test]
+fn test_import_pywal() {
+    let json = r##"{
+        "wallpaper": "/tmp/wall.png",
+        "alpha": "100",
+        "special": { "background": "#1e1e2e", "foreground": "#cdd6f4", "cursor": "#f5e0dc" },
+        "colors": {
+            "color0": "#45475a", "color1": "#f38ba8", "color2": "#a6e3a1", "color3": "#f9e2af",
+            "color4": "#89b4fa", "color5": "#f5c2e7", "color6": "#94e2d5", "color7": "#bac2de",
+            "color8": "#585b70", "color9": "#f38ba8", "color10": "#a6e3a1", "color11": "#f9e2af",
+            "color12": "#89b4fa", "color13": "#f5c2e7", "color14": "#94e2d5", "color15": "#a6adc8"
+        }
+    }"##;
+    let colors: PywalColors = serde_json::from_str(json).unwrap();
+    let theme = from_pywal(&colors).unwrap();
+    assert_eq!(theme.mantle, "1e1e2e");
+    assert_eq!(theme.primary, "89b4fa");
+    assert_eq!(theme.bad, "f38ba8");
+    assert_eq!(theme.primary_contrast, "000000");
+    assert_eq!(theme.rose, "f38ba8");
+    assert_eq!(theme.mauve, "f5c2e7");
+    assert_ne!(theme.lavender, theme.blue);
+}
+
+#[test]
+fn test_import_matugen() {
+    let roles = [
+        ("primary", "#d0bcff", "#6750a4"),
+        ("on_primary", "#381e72", "#ffffff"),
+        ("secondary", "#ccc2dc", "#625b71"),
+        ("on_secondary", "#332d41", "#ffffff"),
+        ("tertiary", "#efb8c8", "#7d5260"),
+        ("on_tertiary", "#492532", "#ffffff"),
+        ("error", "#f2b8b5", "#b3261e"),
+        ("on_error", "#601410", "#ffffff"),
+        ("surface", "#141218", "#fef7ff"),
+        ("surface_variant", "#49454f", "#e7e0ec"),
+        ("on_surface", "#e6e0e9", "#1d1b20"),
+        ("on_surface_variant", "#cac4d0", "#49454f"),
+        ("outline_variant", "#49454f", "#cac4d0"),
+    ];
+    // matugen 2.4 and later
+    let by_color = roles
+        .iter()
+        .map(|(name, dark, light)| {
+            format!(r#""{name}": {{ "dark": "{dark}", "light": "{light}" }}"#)
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let output: MatugenOutput =
+        serde_json::from_str(&format!(r#"{{ "colors": {{ {by_color} }} }}"#)).unwrap();
+    let theme = from_matugen(&output, false).unwrap();
+    assert_eq!(theme.primary, "6750a4");
+    assert_eq!(theme.mantle, "fef7ff");
+    // no surface containers before matugen 2.0
+    assert_eq!(theme.base, "e7e0ec");
+
+    // matugen up to 2.3
+    let variant = |dark: bool| {
+        roles
+            .iter()
+            .map(|(name, dark_hex, light_hex)| {
+                format!(r#""{name}": "{}""#, if dark { dark_hex } else { light_hex })
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let json = format!(
+        r#"{{ "colors": {{ "dark": {{ {} }}, "light": {{ {} }} }} }}"#,
+        variant(true),
+        variant(false)
+    );
+    let output: MatugenOutput = serde_json::from_str(&json).unwrap();
+    let theme = from_matugen(&output, true).unwrap();
+    assert_eq!(theme.primary, "d0bcff");
+    assert_eq!(theme.text, "e6e0e9");
+    assert_eq!(theme.bad_contrast, "601410");
+}
+
+#[test]
+fn test_watcher_reports_changed_sources() {
+    use std::fs::{File, write};
+
+    let path = std::env::temp_dir().join(format!("oxiced-watch-{}.json", std::process::id()));
+    write(&path, "{}").unwrap();
+    let mut config = ImportConfig {
+        source: ColorSource::Pywal,
+        path: Some(path.clone()),
+        dark: None,
+        watch: None,
+    };
+    assert!(config.watcher().is_none());
+    config.watch = Some(true);
+    let mut watcher = config.watcher().unwrap();
+    assert!(!watcher.check());
+
+    let later = SystemTime::now() + Duration::from_secs(10);
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    assert!(watcher.check());
+    assert!(!watcher.check());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -5,15 +5,14 @@ use iced::{
         palette::{Background, Danger, Extended, Pair, Primary, Secondary, Success, Warning},
     },
 };
-use once_cell::sync::Lazy;
 use serde::{Deserialize, de::DeserializeOwned};
 
 use crate::{
-    theme::theme_impl::OXITHEME,
-    utils::{
-        color::{darken_color, lighten_color},
-        file::get_theme_toml,
+    theme::{
+        seed::contrast_hex,
+        theme_impl::{OXITHEME, OxiTheme, default_theme, get_derived_iced_theme},
     },
+    utils::color::{darken_color, lighten_color, mix_oklab, mk_color, to_hex},
 };
 
 pub fn get_all_themes() -> Vec<Theme> {
@@ -385,6 +384,43 @@ impl From<TomlBase16> for Extended {
     }
 }
 
+/// Follows the base16 styling guidelines, with catppuccin as the reference for the accents
+impl From<TomlBase16> for OxiTheme {
+    fn from(val: TomlBase16) -> Self {
+        let contrast = |hex: &str| contrast_hex(&mk_color(hex));
+        OxiTheme {
+            base: val.base02.clone(),
+            mantle: val.base00.clone(),
+            primary_bg: val.base01.clone(),
+            secondary_bg: val.base02.clone(),
+            tertiary_bg: val.base03.clone(),
+            text: val.base05.clone(),
+            text_muted: val.base04.clone(),
+            primary_contrast: contrast(&val.base0d),
+            secondary_contrast: contrast(&val.base0e),
+            good_contrast: contrast(&val.base0b),
+            bad_contrast: contrast(&val.base08),
+            info_contrast: contrast(&val.base0c),
+            warning_contrast: contrast(&val.base0a),
+            rose: val.base06.clone(),
+            lavender: val.base07.clone(),
+            blue: val.base0d.clone(),
+            mauve: val.base0e.clone(),
+            flamingo: val.base0f,
+            border_color_weak: val.base05,
+            border_color_strong: val.base0d.clone(),
+            primary: val.base0d,
+            secondary: val.base0e,
+            good: val.base0b,
+            bad: val.base08,
+            info: val.base0c,
+            warning: val.base0a,
+            name: val.name,
+            ..default_theme()
+        }
+    }
+}
+
 impl TomlTheme for TomlBase16 {
     fn name(&self) -> String {
         self.name.clone()
@@ -414,54 +450,47 @@ impl From<TomlSimple> for Palette {
     }
 }
 
+impl From<TomlSimple> for OxiTheme {
+    fn from(val: TomlSimple) -> Self {
+        let primary = color!(val.primary);
+        let pair = |color: u32| (to_hex(&color!(color)), contrast_hex(&color!(color)));
+        let (good, good_contrast) = pair(val.success);
+        let (bad, bad_contrast) = pair(val.danger);
+        let (warning, warning_contrast) = pair(val.warning);
+        OxiTheme {
+            name: val.name,
+            primary: to_hex(&primary),
+            primary_contrast: contrast_hex(&primary),
+            border_color_strong: to_hex(&primary),
+            good,
+            good_contrast,
+            bad,
+            bad_contrast,
+            warning,
+            warning_contrast,
+            ..OxiTheme::from_background(color!(val.background), color!(val.text), primary)
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct TomlExtended {
     name: String,
     is_dark: bool,
-    background: TomlPalette,
+    background: TomlBackground,
     primary: TomlPalette,
     secondary: TomlPalette,
     success: TomlPalette,
     danger: TomlPalette,
+    /// Older themes without warning keep using the danger colors
+    warning: Option<TomlPalette>,
 }
 
 impl From<TomlExtended> for Extended {
     fn from(val: TomlExtended) -> Self {
+        let warning = val.warning.unwrap_or(val.danger);
         Extended {
-            background: Background {
-                base: Pair {
-                    color: color!(val.background.base.color),
-                    text: color!(val.background.base.text),
-                },
-                weak: Pair {
-                    color: color!(val.background.weak.color),
-                    text: color!(val.background.weak.text),
-                },
-                weaker: Pair {
-                    color: color!(val.background.weak.color),
-                    text: color!(val.background.weak.text),
-                },
-                strong: Pair {
-                    color: color!(val.background.strong.color),
-                    text: color!(val.background.strong.text),
-                },
-                stronger: Pair {
-                    color: color!(val.background.strong.color),
-                    text: color!(val.background.strong.text),
-                },
-                weakest: Pair {
-                    color: color!(val.background.strong.color),
-                    text: color!(val.background.strong.text),
-                },
-                strongest: Pair {
-                    color: color!(val.background.strong.color),
-                    text: color!(val.background.strong.text),
-                },
-                neutral: Pair {
-                    color: color!(val.background.base.color),
-                    text: color!(val.background.base.text),
-                },
-            },
+            background: val.background.into(),
             primary: Primary {
                 base: Pair {
                     color: color!(val.primary.base.color),
@@ -520,73 +549,152 @@ impl From<TomlExtended> for Extended {
             },
             is_dark: val.is_dark,
             warning: Warning {
-                base: Pair {
-                    color: color!(val.danger.base.color),
-                    text: color!(val.danger.base.text),
-                },
-                weak: Pair {
-                    color: color!(val.danger.weak.color),
-                    text: color!(val.danger.weak.text),
-                },
-                strong: Pair {
-                    color: color!(val.danger.strong.color),
-                    text: color!(val.danger.strong.text),
-                },
+                base: warning.base.into(),
+                weak: warning.weak.into(),
+                strong: warning.strong.into(),
             },
         }
     }
 }
 
+/// Background levels map onto the surfaces, info is derived from primary
+impl From<TomlExtended> for OxiTheme {
+    fn from(val: TomlExtended) -> Self {
+        let name = val.name.clone();
+        let extended = Extended::from(val);
+        let background = extended.background;
+        let hex = |pair: Pair| to_hex(&pair.color);
+        let text = |pair: Pair| to_hex(&pair.text);
+        OxiTheme {
+            name,
+            base: hex(background.neutral),
+            primary_bg: hex(background.weakest),
+            secondary_bg: hex(background.weak),
+            tertiary_bg: hex(background.strong),
+            primary: hex(extended.primary.base),
+            primary_contrast: text(extended.primary.base),
+            secondary: hex(extended.secondary.base),
+            secondary_contrast: text(extended.secondary.base),
+            good: hex(extended.success.base),
+            good_contrast: text(extended.success.base),
+            bad: hex(extended.danger.base),
+            bad_contrast: text(extended.danger.base),
+            warning: hex(extended.warning.base),
+            warning_contrast: text(extended.warning.base),
+            border_color_strong: hex(extended.primary.base),
+            ..OxiTheme::from_background(
+                background.base.color,
+                background.base.text,
+                extended.primary.base.color,
+            )
+        }
+    }
+}
+
 impl TomlTheme for TomlExtended {
     fn name(&self) -> String {
         self.name.clone()
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone, Copy)]
 pub struct TomlPalette {
     base: TomlPair,
     weak: TomlPair,
     strong: TomlPair,
 }
 
+/// Background levels of [`TomlExtended`], only base, weak and strong are required
 #[derive(Deserialize)]
-pub struct TomlPair {
-    color: u32,
-    text: u32,
+pub struct TomlBackground {
+    base: TomlPair,
+    weakest: Option<TomlPair>,
+    weaker: Option<TomlPair>,
+    weak: TomlPair,
+    neutral: Option<TomlPair>,
+    strong: TomlPair,
+    stronger: Option<TomlPair>,
+    strongest: Option<TomlPair>,
 }
 
-pub fn get_theme() -> Theme {
-    THEME.clone()
+// Distance of every level from base, the same steps iced uses in Background::new
+const WEAKEST: f32 = 0.03;
+const WEAKER: f32 = 0.07;
+const WEAK: f32 = 0.1;
+const NEUTRAL: f32 = 0.125;
+const STRONG: f32 = 0.15;
+const STRONGER: f32 = 0.175;
+const STRONGEST: f32 = 0.2;
+
+impl From<TomlBackground> for Background {
+    fn from(val: TomlBackground) -> Self {
+        let mut known: Vec<(f32, Pair)> = [
+            (0.0, Some(val.base)),
+            (WEAKEST, val.weakest),
+            (WEAKER, val.weaker),
+            (WEAK, Some(val.weak)),
+            (NEUTRAL, val.neutral),
+            (STRONG, Some(val.strong)),
+            (STRONGER, val.stronger),
+            (STRONGEST, val.strongest),
+        ]
+        .into_iter()
+        .filter_map(|(level, pair)| pair.map(|pair| (level, pair.into())))
+        .collect();
+        known.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let level = |level: f32| interpolate_level(&known, level);
+        Background {
+            base: level(0.0),
+            weakest: level(WEAKEST),
+            weaker: level(WEAKER),
+            weak: level(WEAK),
+            neutral: level(NEUTRAL),
+            strong: level(STRONG),
+            stronger: level(STRONGER),
+            strongest: level(STRONGEST),
+        }
+    }
 }
 
-static THEME: Lazy<Theme> = Lazy::new(|| {
-    let theme_string = get_theme_toml();
-    if theme_string.is_err() {
-        return Theme::custom_with_fn(TOKYO_NIGHT_DARK_NAME, TOKYO_NIGHT_DARK, tokyo_generate);
+/// Interpolates a missing level between its neighbours, levels above the strongest given one
+/// continue the trend of the two strongest given ones. The text is taken from the closest level.
+fn interpolate_level(known: &[(f32, Pair)], level: f32) -> Pair {
+    if let Some((_, pair)) = known.iter().find(|(known, _)| *known == level) {
+        return *pair;
     }
-    let theme_string = theme_string.unwrap();
-    let base16_theme = parse_extended_palette::<TomlBase16>(&theme_string);
-    if let Ok(theme) = base16_theme {
-        return theme;
+    let upper = known.iter().position(|(known, _)| *known > level);
+    let (lower, upper) = match upper {
+        Some(0) => (&known[0], &known[1]),
+        Some(upper) => (&known[upper - 1], &known[upper]),
+        None => (&known[known.len() - 2], &known[known.len() - 1]),
+    };
+    let factor = (level - lower.0) / (upper.0 - lower.0);
+    let closest = if factor < 0.5 { lower } else { upper };
+    Pair {
+        color: mix_oklab(&lower.1.color, &upper.1.color, factor),
+        text: closest.1.text,
     }
-    let theme = parse_simple_palette(&theme_string);
-    if let Ok(theme) = theme {
-        return theme;
-    }
-    let extended_theme = parse_extended_palette::<TomlExtended>(&theme_string);
-    if let Ok(theme) = extended_theme {
-        return theme;
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct TomlPair {
+    color: u32,
+    text: u32,
+}
+
+impl From<TomlPair> for Pair {
+    fn from(val: TomlPair) -> Self {
+        Pair {
+            color: color!(val.color),
+            text: color!(val.text),
+        }
     }
-    Theme::custom_with_fn(TOKYO_NIGHT_DARK_NAME, TOKYO_NIGHT_DARK, tokyo_generate)
-});
-
-fn parse_simple_palette(theme_string: &str) -> Result<Theme, toml::de::Error> {
-    let parsed_theme: TomlSimple = toml::from_str(theme_string)?;
-    Ok(Theme::custom(
-        parsed_theme.name.clone(),
-        parsed_theme.into(),
-    ))
+}
+
+/// Derived from [`OXITHEME`], so iced and oxi widgets always agree on the scheme,
+/// also after [`reload_theme`](crate::theme::theme_impl::reload_theme)
+pub fn get_theme() -> Theme {
+    get_derived_iced_theme()
 }
 
 pub fn parse_extended_palette<T: DeserializeOwned + TomlTheme + Into<Extended>>(
@@ -710,3 +818,80 @@ fn tokyo_generate(palette: Palette) -> Extended {
         },
     }
 }
+
+#[test]
+fn test_extended_interpolates_background_levels() {
+    let theme: TomlExtended = toml::from_str(
+        r#"
+        name = "test"
+        is_dark = true
+        background.base = { color = 0x000000, text = 0xffffff }
+        background.weak = { color = 0x404040, text = 0xffffff }
+        background.strong = { color = 0x808080, text = 0x000000 }
+        background.strongest = { color = 0xffffff, text = 0x000000 }
+        primary.base = { color = 0x89b4fa, text = 0x000000 }
+        primary.weak = { color = 0x89b4fa, text = 0x000000 }
+        primary.strong = { color = 0x89b4fa, text = 0x000000 }
+        secondary.base = { color = 0xb4befe, text = 0x000000 }
+        secondary.weak = { color = 0xb4befe, text = 0x000000 }
+        secondary.strong = { color = 0xb4befe, text = 0x000000 }
+        success.base = { color = 0xa6e3a1, text = 0x000000 }
+        success.weak = { color = 0xa6e3a1, text = 0x000000 }
+        success.strong = { color = 0xa6e3a1, text = 0x000000 }
+        danger.base = { color = 0xf38ba8, text = 0x000000 }
+        danger.weak = { color = 0xf38ba8, text = 0x000000 }
+        danger.strong = { color = 0xf38ba8, text = 0x000000 }
+        warning.base = { color = 0xf9e2af, text = 0x000000 }
+        warning.weak = { color = 0xf9e2af, text = 0x000000 }
+        warning.strong = { color = 0xf9e2af, text = 0x000000 }
+        "#,
+    )
+    .unwrap();
+    let extended = Extended::from(theme);
+    let background = extended.background;
+    let lightness = |pair: Pair| crate::utils::color::to_oklab(&pair.color)[0];
+    // every level lies strictly between its neighbours instead of copying one of them
+    assert!(lightness(background.base) < lightness(background.weakest));
+    assert!(lightness(background.weakest) < lightness(background.weaker));
+    assert!(lightness(background.weaker) < lightness(background.weak));
+    assert!(lightness(background.weak) < lightness(background.neutral));
+    assert!(lightness(background.neutral) < lightness(background.strong));
+    assert!(lightness(background.strong) < lightness(background.stronger));
+    assert!(lightness(background.stronger) < lightness(background.strongest));
+    assert_eq!(extended.warning.base.color, color!(0xf9e2af));
+}
+
+#[test]
+fn test_base16_and_oxi_agree() {
+    use crate::theme::format::parse_theme;
+
+    let theme = parse_theme(
+        r#"
+        name = "Catppuccin Mocha"
+        base00 = "1e1e2e"
+        base01 = "181825"
+        base02 = "313244"
+        base03 = "45475a"
+        base04 = "585b70"
+        base05 = "cdd6f4"
+        base06 = "f5e0dc"
+        base07 = "b4befe"
+        base08 = "f38ba8"
+        base09 = "fab387"
+        base0a = "f9e2af"
+        base0b = "a6e3a1"
+        base0c = "94e2d5"
+        base0d = "89b4fa"
+        base0e = "cba6f7"
+        base0f = "f2cdcd"
+        "#,
+    )
+    .unwrap();
+    let default = default_theme();
+    assert_eq!(theme.name, "Catppuccin Mocha");
+    assert_eq!(theme.mantle, default.mantle);
+    assert_eq!(theme.primary_bg, default.primary_bg);
+    assert_eq!(theme.text, default.text);
+    assert_eq!(theme.primary, default.primary);
+    assert_eq!(theme.bad, default.bad);
+}
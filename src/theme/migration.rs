@@ -0,0 +1,235 @@
+use std::{fmt, path::PathBuf};
+
+use toml::{Table, Value};
+
+use crate::{
+    theme::format::{ThemeFormat, detect_format},
+    utils::file::{get_theme_toml, write_theme_toml},
+};
+
+/// Schema version of [`OxiTheme`](crate::theme::theme_impl::OxiTheme),
+/// theme files without a `version` key are version 1.
+///
+/// Version history:
+/// 1. initial schema
+/// 2. `border_radius` split into `radius_input`, `radius_container` and `radius_window`,
+///    controls got their own `radius_control`
+/// 3. `[button.primary_bg]` and `[button.secondary_bg]` renamed to the variant names
+///    `[button.surface]` and `[button.neutral]`
+pub const CURRENT_VERSION: u32 = 3;
+
+/// Upgrades a theme from `from` to `from + 1`
+struct Migration {
+    from: u32,
+    migrate: fn(&mut Table) -> Vec<Deprecation>,
+}
+
+const MIGRATIONS: [Migration; 2] = [
+    Migration {
+        from: 1,
+        migrate: split_border_radius,
+    },
+    Migration {
+        from: 2,
+        migrate: rename_button_variants,
+    },
+];
+
+/// Keys that only exist in older versions
+pub const DEPRECATED_KEYS: [&str; 1] = ["border_radius"];
+
+/// A key of an older schema that was found and rewritten
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecation {
+    pub key: String,
+    /// Version that replaced the key
+    pub since: u32,
+    pub replacement: String,
+}
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is deprecated since version {}, use {} instead",
+            self.key, self.since, self.replacement
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The theme was written for a newer oxiced
+    Unsupported(u32),
+    InvalidVersion(Value),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Unsupported(version) => write!(
+                f,
+                "theme version {version} is newer than the supported version {CURRENT_VERSION}"
+            ),
+            MigrationError::InvalidVersion(value) => write!(f, "invalid theme version {value}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// A theme upgraded to [`CURRENT_VERSION`]
+pub struct Migrated {
+    pub table: Table,
+    pub from_version: u32,
+    pub deprecations: Vec<Deprecation>,
+}
+
+/// Runs every migration between the version of the theme and [`CURRENT_VERSION`]
+pub fn migrate(mut table: Table) -> Result<Migrated, MigrationError> {
+    let from_version = match table.get("version") {
+        None => 1,
+        Some(value @ Value::Integer(version)) if *version >= 1 => {
+            u32::try_from(*version).map_err(|_| MigrationError::InvalidVersion(value.clone()))?
+        }
+        Some(value) => return Err(MigrationError::InvalidVersion(value.clone())),
+    };
+    if from_version > CURRENT_VERSION {
+        return Err(MigrationError::Unsupported(from_version));
+    }
+    let deprecations = MIGRATIONS
+        .iter()
+        .filter(|migration| migration.from >= from_version)
+        .flat_map(|migration| (migration.migrate)(&mut table))
+        .collect();
+    table.insert(
+        String::from("version"),
+        Value::Integer(CURRENT_VERSION.into()),
+    );
+    Ok(Migrated {
+        table,
+        from_version,
+        deprecations,
+    })
+}
+
+/// Upgrades theme.toml in place, the old file is kept as theme.toml.bak.
+/// Returns the path if the file had to be rewritten.
+/// Comments and key order are not preserved.
+pub fn rewrite_theme_toml() -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+    match migrate_theme_string(&get_theme_toml()?)? {
+        Some(contents) => Ok(Some(write_theme_toml(&contents)?)),
+        None => Ok(None),
+    }
+}
+
+/// The upgraded theme, or None if it is current or not in the oxi format.
+/// The other formats are not versioned and stay untouched.
+pub fn migrate_theme_string(
+    theme_string: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    if detect_format(theme_string)? != ThemeFormat::Oxi {
+        return Ok(None);
+    }
+    let migrated = migrate(toml::from_str(theme_string)?)?;
+    if migrated.from_version == CURRENT_VERSION {
+        return Ok(None);
+    }
+    Ok(Some(toml::to_string(&migrated.table)?))
+}
+
+/// Version 2 has one radius per component size. Controls like checkboxes had their own
+/// fixed radius, only the sizes that used the old radius start with it.
+fn split_border_radius(table: &mut Table) -> Vec<Deprecation> {
+    let Some(radius) = table.remove("border_radius") else {
+        return Vec::new();
+    };
+    let radius = match radius {
+        Value::Integer(radius) => Value::Float(radius as f64),
+        radius => radius,
+    };
+    let replacements = ["radius_input", "radius_container", "radius_window"];
+    for key in replacements {
+        table.entry(key).or_insert_with(|| radius.clone());
+    }
+    vec![Deprecation {
+        key: String::from("border_radius"),
+        since: 2,
+        replacement: replacements.join(", "),
+    }]
+}
+
+/// Version 3 names button overrides after the shared widget variants
+fn rename_button_variants(table: &mut Table) -> Vec<Deprecation> {
+    let Some(Value::Table(buttons)) = table.get_mut("button") else {
+        return Vec::new();
+    };
+    [("primary_bg", "surface"), ("secondary_bg", "neutral")]
+        .into_iter()
+        .filter_map(|(old, new)| {
+            let style = buttons.remove(old)?;
+            buttons.entry(new).or_insert(style);
+            Some(Deprecation {
+                key: format!("button.{old}"),
+                since: 3,
+                replacement: format!("button.{new}"),
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_migrate_border_radius() {
+    let table: Table = toml::from_str("border_radius = 6\nradius_window = 12.0").unwrap();
+    let migrated = migrate(table).unwrap();
+    assert_eq!(migrated.from_version, 1);
+    assert_eq!(migrated.deprecations.len(), 1);
+    assert!(!migrated.table.contains_key("border_radius"));
+    assert_eq!(migrated.table["radius_input"], Value::Float(6.0));
+    assert!(!migrated.table.contains_key("radius_control"));
+    // keys already written for the new schema are kept
+    assert_eq!(migrated.table["radius_window"], Value::Float(12.0));
+    assert_eq!(
+        migrated.table["version"],
+        Value::Integer(CURRENT_VERSION.into())
+    );
+}
+
+#[test]
+fn test_migrate_button_variants() {
+    let table: Table =
+        toml::from_str("version = 2\n[button.primary_bg]\nbackground = \"ff0000\"").unwrap();
+    let migrated = migrate(table).unwrap();
+    assert_eq!(migrated.deprecations.len(), 1);
+    let buttons = migrated.table["button"].as_table().unwrap();
+    assert!(!buttons.contains_key("primary_bg"));
+    assert!(buttons.contains_key("surface"));
+}
+
+#[test]
+fn test_only_oxi_themes_are_rewritten() {
+    let simple = "# comment\nname = \"dark\"\nbackground = 0x000000\ntext = 0xffffff\n\
+        primary = 0x0000ff\nsuccess = 0x00ff00\ndanger = 0xff0000\nwarning = 0xffff00";
+    assert_eq!(migrate_theme_string(simple).unwrap(), None);
+    let current = format!("version = {CURRENT_VERSION}\nradius_input = 4.0");
+    assert_eq!(migrate_theme_string(&current).unwrap(), None);
+    let outdated = migrate_theme_string("border_radius = 6").unwrap().unwrap();
+    assert!(outdated.contains("radius_input = 6.0"));
+}
+
+#[test]
+fn test_invalid_versions_are_rejected() {
+    let migrate_version = |version: &str| migrate(toml::from_str(version).unwrap());
+    assert!(matches!(
+        migrate_version("version = 4294967297"),
+        Err(MigrationError::InvalidVersion(_))
+    ));
+    assert!(matches!(
+        migrate_version("version = 0"),
+        Err(MigrationError::InvalidVersion(_))
+    ));
+    assert!(matches!(
+        migrate_version(&format!("version = {}", CURRENT_VERSION + 1)),
+        Err(MigrationError::Unsupported(_))
+    ));
+}
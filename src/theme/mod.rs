@@ -1,2 +1,8 @@
+pub mod format;
+pub mod import;
 pub mod legacy_theme;
+pub mod migration;
+pub mod seed;
+pub mod style_override;
 pub mod theme_impl;
+pub mod wallpaper;
@@ -0,0 +1,169 @@
+use iced::Color;
+
+use crate::{
+    theme::theme_impl::{OxiTheme, default_theme},
+    utils::color::{ensure_contrast, from_oklch, mix_oklab, readable_on, to_hex, to_oklch},
+};
+
+/// OKLCH hues of the semantic colors, kept fixed so that red always means error
+const HUE_GOOD: f32 = 145.0;
+const HUE_BAD: f32 = 20.0;
+const HUE_INFO: f32 = 195.0;
+const HUE_WARNING: f32 = 85.0;
+const HUE_ROSE: f32 = 30.0;
+const HUE_LAVENDER: f32 = 280.0;
+const HUE_BLUE: f32 = 255.0;
+const HUE_MAUVE: f32 = 305.0;
+const HUE_FLAMINGO: f32 = 10.0;
+
+/// Offset between the hue of primary and secondary
+const SECONDARY_HUE_SHIFT: f32 = 30.0;
+/// Chroma of the surfaces, just enough to carry the hue of the seed
+const SURFACE_CHROMA: f32 = 0.02;
+const ACCENT_CHROMA_MIN: f32 = 0.08;
+const ACCENT_CHROMA_MAX: f32 = 0.2;
+const PASTEL_CHROMA: f32 = 0.06;
+/// OKLCH lightness steps from a given background towards the text for the surfaces
+const PRIMARY_BG_STEP: f32 = -0.03;
+const SECONDARY_BG_STEP: f32 = 0.08;
+const TERTIARY_BG_STEP: f32 = 0.2;
+/// Muted text is mixed from the text towards the background but has to stay readable
+const TEXT_MUTED_MIX: f32 = 0.4;
+const TEXT_MUTED_CONTRAST: f32 = 4.5;
+
+/// OKLCH lightness of every derived role, dark and light
+struct Tones {
+    base: f32,
+    mantle: f32,
+    primary_bg: f32,
+    secondary_bg: f32,
+    tertiary_bg: f32,
+    text: f32,
+    text_muted: f32,
+    accent: f32,
+    pastel: f32,
+}
+
+const DARK_TONES: Tones = Tones {
+    base: 0.32,
+    mantle: 0.24,
+    primary_bg: 0.21,
+    secondary_bg: 0.32,
+    tertiary_bg: 0.46,
+    text: 0.9,
+    text_muted: 0.65,
+    accent: 0.78,
+    pastel: 0.9,
+};
+
+const LIGHT_TONES: Tones = Tones {
+    base: 0.9,
+    mantle: 0.97,
+    primary_bg: 0.93,
+    secondary_bg: 0.88,
+    tertiary_bg: 0.8,
+    text: 0.25,
+    text_muted: 0.48,
+    accent: 0.55,
+    pastel: 0.7,
+};
+
+impl OxiTheme {
+    /// Generates a complete theme from a single seed color, similar to Material You.
+    ///
+    /// All roles are derived in OKLCH, so every accent has the same perceived lightness
+    /// and the surfaces keep a hint of the seed hue.
+    /// Contrast colors are black or white, whichever is more readable.
+    /// Everything that is not a color is taken from the default theme.
+    pub fn from_seed(seed: Color, dark: bool) -> OxiTheme {
+        let [_, seed_chroma, hue] = to_oklch(&seed);
+        let tones = if dark { &DARK_TONES } else { &LIGHT_TONES };
+        let chroma = seed_chroma.clamp(ACCENT_CHROMA_MIN, ACCENT_CHROMA_MAX);
+
+        let surface = |lightness: f32| to_hex(&from_oklch(lightness, SURFACE_CHROMA, hue));
+        let accent = |hue: f32| from_oklch(tones.accent, chroma, hue);
+        let pastel = |hue: f32| to_hex(&from_oklch(tones.pastel, PASTEL_CHROMA, hue));
+
+        let primary = accent(hue);
+        let secondary = accent(hue + SECONDARY_HUE_SHIFT);
+        let good = accent(HUE_GOOD);
+        let bad = accent(HUE_BAD);
+        let info = accent(HUE_INFO);
+        let warning = accent(HUE_WARNING);
+
+        OxiTheme {
+            base: surface(tones.base),
+            mantle: surface(tones.mantle),
+            primary_bg: surface(tones.primary_bg),
+            secondary_bg: surface(tones.secondary_bg),
+            tertiary_bg: surface(tones.tertiary_bg),
+            text: surface(tones.text),
+            text_muted: surface(tones.text_muted),
+            primary: to_hex(&primary),
+            secondary: to_hex(&secondary),
+            primary_contrast: contrast_hex(&primary),
+            secondary_contrast: contrast_hex(&secondary),
+            good: to_hex(&good),
+            good_contrast: contrast_hex(&good),
+            bad: to_hex(&bad),
+            bad_contrast: contrast_hex(&bad),
+            info: to_hex(&info),
+            info_contrast: contrast_hex(&info),
+            warning: to_hex(&warning),
+            warning_contrast: contrast_hex(&warning),
+            rose: pastel(HUE_ROSE),
+            lavender: pastel(HUE_LAVENDER),
+            blue: pastel(HUE_BLUE),
+            mauve: pastel(HUE_MAUVE),
+            flamingo: pastel(HUE_FLAMINGO),
+            border_color_weak: surface(tones.text),
+            border_color_strong: to_hex(&primary),
+            ..default_theme()
+        }
+    }
+
+    /// Like [`OxiTheme::from_seed`], but keeps the given background and text and derives
+    /// the surfaces from them, for sources that only define a handful of colors.
+    pub fn from_background(background: Color, text: Color, seed: Color) -> OxiTheme {
+        let dark = to_oklch(&background)[0] < to_oklch(&text)[0];
+        let [lightness, chroma, hue] = to_oklch(&background);
+        let surface = |step: f32| {
+            let step = if dark { step } else { -step };
+            to_hex(&from_oklch((lightness + step).clamp(0.0, 1.0), chroma, hue))
+        };
+        let muted = ensure_contrast(
+            &mix_oklab(&text, &background, TEXT_MUTED_MIX),
+            &background,
+            TEXT_MUTED_CONTRAST,
+        );
+        OxiTheme {
+            base: surface(SECONDARY_BG_STEP),
+            mantle: to_hex(&background),
+            primary_bg: surface(PRIMARY_BG_STEP),
+            secondary_bg: surface(SECONDARY_BG_STEP),
+            tertiary_bg: surface(TERTIARY_BG_STEP),
+            text: to_hex(&text),
+            text_muted: to_hex(&muted),
+            border_color_weak: to_hex(&text),
+            ..OxiTheme::from_seed(seed, dark)
+        }
+    }
+}
+
+pub(crate) fn contrast_hex(background: &Color) -> String {
+    to_hex(&readable_on(background))
+}
+
+#[test]
+fn test_seed_theme_is_readable() {
+    use crate::utils::color::{contrast_ratio, mk_color};
+
+    for dark in [true, false] {
+        let theme = OxiTheme::from_seed(mk_color("e64553"), dark);
+        let text = mk_color(&theme.text);
+        assert!(contrast_ratio(&text, &mk_color(&theme.mantle)) >= 7.0);
+        assert!(contrast_ratio(&text, &mk_color(&theme.primary_bg)) >= 4.5);
+        let primary = mk_color(&theme.primary);
+        assert!(contrast_ratio(&mk_color(&theme.primary_contrast), &primary) >= 4.5);
+    }
+}
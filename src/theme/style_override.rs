@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use iced::{Background, Border, Color, border::Radius};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::color::mk_color;
+
+/// Optional per widget table in theme.toml, merged over the default style of that widget.
+/// Overrides are applied last, after the adjustments of high contrast mode.
+///
+/// Supported tables:
+/// - `[button.<variant>]` any [`Variant`](crate::widgets::common::Variant) name, e.g. primary,
+///   danger, neutral or surface
+/// - `[checkbox.<state>]` checked, unchecked, hovered, disabled
+/// - `[text_input.<state>]` active, hovered, focused, disabled
+/// - `[picklist.<state>]` active, hovered, opened, menu
+#[derive(Deserialize, Serialize, Default, Clone)]
+pub struct StyleOverride {
+    pub background: Option<String>,
+    /// Text or icon drawn on top of the background
+    pub text: Option<String>,
+    pub border_color: Option<String>,
+    pub border_width: Option<f32>,
+    pub border_radius: Option<f32>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ComputedStyleOverride {
+    pub background: Option<Color>,
+    pub text: Option<Color>,
+    pub border_color: Option<Color>,
+    pub border_width: Option<f32>,
+    pub border_radius: Option<f32>,
+}
+
+impl From<&StyleOverride> for ComputedStyleOverride {
+    fn from(value: &StyleOverride) -> Self {
+        Self {
+            background: value.background.as_deref().map(mk_color),
+            text: value.text.as_deref().map(mk_color),
+            border_color: value.border_color.as_deref().map(mk_color),
+            border_width: value.border_width,
+            border_radius: value.border_radius,
+        }
+    }
+}
+
+impl ComputedStyleOverride {
+    pub fn background(&self, background: Background) -> Background {
+        self.background.map(Background::Color).unwrap_or(background)
+    }
+
+    pub fn text(&self, text: Color) -> Color {
+        self.text.unwrap_or(text)
+    }
+
+    pub fn border(&self, border: Border) -> Border {
+        Border {
+            color: self.border_color.unwrap_or(border.color),
+            width: self.border_width.unwrap_or(border.width),
+            radius: self
+                .border_radius
+                .map(Radius::from)
+                .unwrap_or(border.radius),
+        }
+    }
+}
+
+pub fn compute_overrides(
+    overrides: &HashMap<String, StyleOverride>,
+) -> HashMap<String, ComputedStyleOverride> {
+    overrides
+        .iter()
+        .map(|(key, value)| (key.clone(), ComputedStyleOverride::from(value)))
+        .collect()
+}
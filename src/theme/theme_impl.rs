@@ -1,30 +1,98 @@
-use iced::{Color, Theme, theme::Palette};
+use std::{collections::HashMap, ops::Deref, sync::RwLock};
+
+use iced::{
+    Color, Shadow, Theme, Vector,
+    animation::Easing,
+    border::Radius,
+    theme::{
+        Palette,
+        palette::{Extended, Pair},
+    },
+    time::Instant,
+};
+use lilt::{Animated, FloatRepresentable};
 use once_cell::sync::Lazy;
-use optional_struct::{Applicable, optional_struct};
-use serde::Deserialize;
+use optional_struct::optional_struct;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    theme::{
+        format::{ThemeError, load_theme},
+        import::ImportConfig,
+        migration::CURRENT_VERSION,
+        style_override::{ComputedStyleOverride, StyleOverride, compute_overrides},
+    },
+    utils::{
+        animation::{mk_easing, reduced_motion_from_env},
+        color::{
+            CONTRAST_AA, CONTRAST_AAA, ensure_contrast_on_all, high_contrast_from_env, mk_color,
+            mk_dark_color, mk_light_color,
+        },
+        color_vision::{
+            ColorVisionDeficiency, indistinguishable_pairs, simulate, simulation_from_env,
+        },
+    },
+};
 
-use crate::utils::{
-    color::{mk_color, mk_dark_color, mk_light_color},
-    file::get_theme_toml,
+/// The active theme, every supported format of theme.toml ends up here.
+/// Both [`get_derived_iced_theme`] and [`get_theme`](crate::theme::legacy_theme::get_theme)
+/// are derived from it.
+pub static OXITHEME: ActiveTheme = ActiveTheme {
+    loaded: Lazy::new(|| {
+        compute_theme(load_theme().unwrap_or_else(|error| {
+            eprintln!("{error}\nfalling back to the default theme");
+            default_theme()
+        }))
+    }),
+    reloaded: RwLock::new(None),
 };
 
-pub static OXITHEME: Lazy<ComputedOxiTheme> = Lazy::new(|| {
-    let default_theme = default_theme();
-    let theme_str_opt = get_theme_toml();
-    if theme_str_opt.is_err() {
-        return ComputedOxiTheme::from(default_theme);
-    };
-    let theme_opt = toml::from_str::<OptionalOxiTheme>(&theme_str_opt.unwrap());
-    let theme = if let Ok(theme) = theme_opt {
-        theme.build(default_theme)
-    } else {
-        default_theme
-    };
-    ComputedOxiTheme::from(theme)
-});
+/// Theme that is loaded on first use and replaced by [`reload_theme`]
+pub struct ActiveTheme {
+    loaded: Lazy<ComputedOxiTheme>,
+    /// Replaced themes are leaked, so references to them stay valid
+    reloaded: RwLock<Option<&'static ComputedOxiTheme>>,
+}
+
+impl Deref for ActiveTheme {
+    type Target = ComputedOxiTheme;
+
+    fn deref(&self) -> &ComputedOxiTheme {
+        let reloaded = *self
+            .reloaded
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        reloaded.unwrap_or(&self.loaded)
+    }
+}
+
+fn compute_theme(theme: OxiTheme) -> ComputedOxiTheme {
+    let theme = ComputedOxiTheme::from(theme);
+    match simulation_from_env() {
+        Some(deficiency) => theme.simulate(deficiency),
+        None => theme,
+    }
+}
+
+/// Loads theme.toml again, including its `[import]` colors, and makes it the active theme.
+///
+/// Return the iced theme from the `theme` function of the app, widgets pick up
+/// the new colors with the next redraw.
+/// On errors the previous theme stays active.
+pub fn reload_theme() -> Result<Theme, ThemeError> {
+    let theme: &'static ComputedOxiTheme = Box::leak(Box::new(compute_theme(load_theme()?)));
+    *OXITHEME
+        .reloaded
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(theme);
+    Ok(iced_theme(theme))
+}
 
 pub fn get_derived_iced_theme() -> Theme {
-    let theme = &OXITHEME;
+    iced_theme(&OXITHEME)
+}
+
+pub fn iced_theme(theme: &ComputedOxiTheme) -> Theme {
     let palette = Palette {
         background: theme.mantle,
         text: theme.text,
@@ -33,11 +101,25 @@ pub fn get_derived_iced_theme() -> Theme {
         danger: theme.bad,
         warning: theme.warning,
     };
-    Theme::custom(String::from("OxiTheme"), palette)
+    let pair = |color: Color, text: Color| Pair { color, text };
+    Theme::custom_with_fn(theme.name.clone(), palette, |palette| {
+        // levels without an oxi counterpart are generated by iced
+        let mut extended = Extended::generate(palette);
+        extended.background.weak = Pair::new(theme.secondary_bg, theme.text);
+        extended.background.strong = Pair::new(theme.tertiary_bg, theme.text);
+        extended.primary.base = pair(theme.primary, theme.primary_contrast);
+        extended.secondary.base = pair(theme.secondary, theme.secondary_contrast);
+        extended.success.base = pair(theme.good, theme.good_contrast);
+        extended.danger.base = pair(theme.bad, theme.bad_contrast);
+        extended.warning.base = pair(theme.warning, theme.warning_contrast);
+        extended
+    })
 }
 
-fn default_theme() -> OxiTheme {
+pub(crate) fn default_theme() -> OxiTheme {
     OxiTheme {
+        version: CURRENT_VERSION,
+        name: String::from("OxiTheme"),
         base: String::from("313244"), // TODO beforepr needed?
         mantle: String::from("1e1e2e"),
         primary_bg: String::from("181825"),
@@ -67,9 +149,34 @@ fn default_theme() -> OxiTheme {
         flamingo: String::from("f2cdcd"),
         tint_amount: 0.04,
         shade_amount: 0.08,
-        border_radius: 10,
+        radius_control: 4.0,
+        radius_input: 10.0,
+        radius_container: 10.0,
+        radius_window: 10.0,
+        border_width_control: 1.0,
+        border_width_input: 1.0,
+        border_width_container: 2.0,
+        border_width_window: 3.0,
         border_color_weak: String::from("cdd6f4"),
         border_color_strong: String::from("89b4fa"),
+        shadows_enabled: true,
+        shadow_low_color: String::from("00000040"),
+        shadow_low_offset: 1.0,
+        shadow_low_blur: 2.0,
+        shadow_medium_color: String::from("00000059"),
+        shadow_medium_offset: 2.0,
+        shadow_medium_blur: 6.0,
+        shadow_high_color: String::from("00000073"),
+        shadow_high_offset: 4.0,
+        shadow_high_blur: 12.0,
+        animation_duration: 100.0,
+        animation_duration_slow: 400.0,
+        animation_easing: String::from("ease_out"),
+        animation_easing_fade: String::from("ease_in_out"),
+        reduced_motion: false,
+        high_contrast: false,
+        high_contrast_border_width: 2.0,
+        focus_border_width: 3.0,
         padding_xs: 4.0,
         padding_sm: 8.0,
         padding_md: 12.0,
@@ -81,34 +188,50 @@ fn default_theme() -> OxiTheme {
         font_lg: 18.0,
         font_xl: 24.0,
         font_xxl: 32.0,
+        icon_theme: String::new(),
+        icon_size: 24,
+        icon_fallback: String::from("image-missing"),
+        bar_height: 36.0,
+        slider_rail_width: 8.0,
+        slider_handle_radius: 8.0,
+        slider_handle_border_width: 2.0,
+        rule_radius: 2.0,
+        button: HashMap::new(),
+        checkbox: HashMap::new(),
+        text_input: HashMap::new(),
+        picklist: HashMap::new(),
+        import: None,
     }
 }
 
-// TODO shadow color
 #[optional_struct]
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct OxiTheme {
+    /// Schema version, older files are migrated on load
+    pub version: u32,
+    /// Shown in theme pickers
+    pub name: String,
     /// TODO beforepr
-    /// Base16: base00 -> base
+    /// Base16: base02 -> surface0
     pub base: String,
     /// Background of an application
-    /// Base16: base01 -> mantle
+    /// Base16: base00 -> base
     pub mantle: String,
     /// Background for sidebars and cards
-    /// Base16: base02 -> surface0
+    /// Base16: base01 -> mantle
     pub primary_bg: String,
     /// Background for selectors/pickers
-    /// Base16: base02 -> surface1
+    /// Base16: base02 -> surface0
     pub secondary_bg: String,
     /// Background for card in cards
-    /// Base16: base03 -> surface2
+    /// Base16: base03 -> surface1
     pub tertiary_bg: String,
     /// General text
-    /// Base16: base04 -> text
+    /// Base16: base05 -> text
     pub text: String,
     /// Muted text
-    /// Base16: base03 -> surface2
+    /// Base16: base04 -> surface2
     pub text_muted: String,
 
     /// Tint color
@@ -118,12 +241,13 @@ pub struct OxiTheme {
     /// darkens something
     pub shade: String,
 
-    // Base 16 ?? where do we take primary??
     /// Primary theme color
     /// Buttons etc
+    /// Base16: base0D -> blue
     pub primary: String,
     /// Secondary theme color
     /// Buttons etc
+    /// Base16: base0E -> mauve
     pub secondary: String,
     /// Contrast text to primary
     pub primary_contrast: String,
@@ -156,12 +280,60 @@ pub struct OxiTheme {
     pub shade_amount: f32,
     pub tint_amount: f32,
 
-    pub border_radius: u32,
+    /// Radius for small controls like checkboxes and radios
+    /// Any radius of at least half the element height renders as a pill
+    pub radius_control: f32,
+    /// Radius for inputs like buttons, text inputs and pick lists
+    pub radius_input: f32,
+    /// Radius for containers like cards and menus
+    pub radius_container: f32,
+    /// Radius for windows and layers
+    pub radius_window: f32,
+    pub border_width_control: f32,
+    pub border_width_input: f32,
+    pub border_width_container: f32,
+    pub border_width_window: f32,
     /// Borders for Selectors and similar
     pub border_color_weak: String,
     /// Borders for hard cuts like sidebars
     pub border_color_strong: String,
 
+    /// Disables every elevation shadow when false, useful for flat themes
+    pub shadows_enabled: bool,
+    /// Shadow for slightly raised elements like buttons and cards
+    /// Hex with optional alpha: rrggbb or rrggbbaa
+    pub shadow_low_color: String,
+    pub shadow_low_offset: f32,
+    pub shadow_low_blur: f32,
+    /// Shadow for floating elements like menus and popups
+    pub shadow_medium_color: String,
+    pub shadow_medium_offset: f32,
+    pub shadow_medium_blur: f32,
+    /// Shadow for elements above everything else like layers and overlays
+    pub shadow_high_color: String,
+    pub shadow_high_offset: f32,
+    pub shadow_high_blur: f32,
+
+    /// Duration in milliseconds of state transitions like toggling
+    pub animation_duration: f32,
+    /// Duration in milliseconds of slower transitions like hover fades
+    pub animation_duration_slow: f32,
+    /// Easing of state transitions, snake case like ease_out or ease_in_out_cubic
+    pub animation_easing: String,
+    /// Easing of hover fades
+    pub animation_easing_fade: String,
+    /// Makes every transition instant
+    /// Also enabled by setting OXICED_REDUCED_MOTION
+    pub reduced_motion: bool,
+
+    /// Accessibility mode with AAA text contrast, strong borders and no translucency
+    /// Also enabled by setting OXICED_HIGH_CONTRAST
+    pub high_contrast: bool,
+    /// Border width of inputs, buttons and similar in high contrast mode
+    pub high_contrast_border_width: f32,
+    /// Border width of focused or hovered elements in high contrast mode
+    pub focus_border_width: f32,
+
     pub padding_xs: f32,
     pub padding_sm: f32,
     pub padding_md: f32,
@@ -174,11 +346,38 @@ pub struct OxiTheme {
     pub font_lg: f32,
     pub font_xl: f32,
     pub font_xxl: f32,
+
+    /// Freedesktop icon theme of [`icon_widget`](crate::widgets::oxi_icon::icon_widget),
+    /// empty to use the icon theme from the GTK settings
+    pub icon_theme: String,
+    /// Size in pixels that icons are looked up for
+    pub icon_size: u32,
+    /// Icon name used when an icon is not found in any theme
+    pub icon_fallback: String,
+
+    /// Thickness of a [`Bar`](crate::widgets::oxi_bar::Bar), its height when horizontal
+    pub bar_height: f32,
+    /// Thickness of the [`slider`](crate::widgets::oxi_slider::slider) rail
+    pub slider_rail_width: f32,
+    pub slider_handle_radius: f32,
+    pub slider_handle_border_width: f32,
+    /// Radius of the ends of [rules](crate::widgets::oxi_rule::horizontal_rule)
+    pub rule_radius: f32,
+
+    /// Per widget style overrides, see [`StyleOverride`]
+    pub button: HashMap<String, StyleOverride>,
+    pub checkbox: HashMap<String, StyleOverride>,
+    pub text_input: HashMap<String, StyleOverride>,
+    pub picklist: HashMap<String, StyleOverride>,
+
+    /// Colors imported from pywal, wallust or matugen on every load
+    pub import: Option<ImportConfig>,
 }
 
 impl From<OxiTheme> for ComputedOxiTheme {
     fn from(value: OxiTheme) -> Self {
-        Self {
+        let theme = Self {
+            name: value.name.clone(),
             base: mk_color(&value.base),
             mantle: mk_color(&value.mantle),
             mantle_hover: mk_light_color(&value.mantle, value.tint_amount),
@@ -227,9 +426,43 @@ impl From<OxiTheme> for ComputedOxiTheme {
             flamingo: mk_color(&value.flamingo),
             shade_amount: value.shade_amount,
             tint_amount: value.tint_amount,
-            border_radius: value.border_radius,
+            radius_control: value.radius_control,
+            radius_input: value.radius_input,
+            radius_container: value.radius_container,
+            radius_window: value.radius_window,
+            border_width_control: value.border_width_control,
+            border_width_input: value.border_width_input,
+            border_width_container: value.border_width_container,
+            border_width_window: value.border_width_window,
             border_color_weak: mk_color(&value.border_color_weak),
             border_color_strong: mk_color(&value.border_color_strong),
+            shadows_enabled: value.shadows_enabled,
+            shadow_low: mk_shadow(
+                value.shadows_enabled,
+                &value.shadow_low_color,
+                value.shadow_low_offset,
+                value.shadow_low_blur,
+            ),
+            shadow_medium: mk_shadow(
+                value.shadows_enabled,
+                &value.shadow_medium_color,
+                value.shadow_medium_offset,
+                value.shadow_medium_blur,
+            ),
+            shadow_high: mk_shadow(
+                value.shadows_enabled,
+                &value.shadow_high_color,
+                value.shadow_high_offset,
+                value.shadow_high_blur,
+            ),
+            animation_duration: value.animation_duration,
+            animation_duration_slow: value.animation_duration_slow,
+            animation_easing: mk_easing(&value.animation_easing),
+            animation_easing_fade: mk_easing(&value.animation_easing_fade),
+            reduced_motion: value.reduced_motion || reduced_motion_from_env(),
+            high_contrast: value.high_contrast || high_contrast_from_env(),
+            high_contrast_border_width: value.high_contrast_border_width,
+            focus_border_width: value.focus_border_width,
             padding_xs: value.padding_xs,
             padding_sm: value.padding_sm,
             padding_md: value.padding_md,
@@ -241,12 +474,32 @@ impl From<OxiTheme> for ComputedOxiTheme {
             font_lg: value.font_lg,
             font_xl: value.font_xl,
             font_xxl: value.font_xxl,
+            icon_theme: value.icon_theme.clone(),
+            icon_size: value.icon_size,
+            icon_fallback: value.icon_fallback.clone(),
+            bar_height: value.bar_height,
+            slider_rail_width: value.slider_rail_width,
+            slider_handle_radius: value.slider_handle_radius,
+            slider_handle_border_width: value.slider_handle_border_width,
+            rule_radius: value.rule_radius,
+            button: compute_overrides(&value.button),
+            checkbox: compute_overrides(&value.checkbox),
+            text_input: compute_overrides(&value.text_input),
+            picklist: compute_overrides(&value.picklist),
+            import: value.import.clone(),
+        };
+        if theme.high_contrast {
+            theme.with_high_contrast()
+        } else {
+            theme
         }
     }
 }
 
 #[allow(dead_code)]
+#[derive(Clone)]
 pub struct ComputedOxiTheme {
+    pub name: String,
     pub base: Color,
 
     pub mantle: Color,
@@ -310,10 +563,32 @@ pub struct ComputedOxiTheme {
     pub shade_amount: f32,
     pub tint_amount: f32,
 
-    pub border_radius: u32,
+    pub radius_control: f32,
+    pub radius_input: f32,
+    pub radius_container: f32,
+    pub radius_window: f32,
+    pub border_width_control: f32,
+    pub border_width_input: f32,
+    pub border_width_container: f32,
+    pub border_width_window: f32,
     pub border_color_weak: Color,
     pub border_color_strong: Color,
 
+    pub shadows_enabled: bool,
+    pub shadow_low: Shadow,
+    pub shadow_medium: Shadow,
+    pub shadow_high: Shadow,
+
+    pub animation_duration: f32,
+    pub animation_duration_slow: f32,
+    pub animation_easing: Easing,
+    pub animation_easing_fade: Easing,
+    pub reduced_motion: bool,
+
+    pub high_contrast: bool,
+    pub high_contrast_border_width: f32,
+    pub focus_border_width: f32,
+
     pub padding_xs: f32,
     pub padding_sm: f32,
     pub padding_md: f32,
@@ -326,4 +601,321 @@ pub struct ComputedOxiTheme {
     pub font_lg: f32,
     pub font_xl: f32,
     pub font_xxl: f32,
+
+    pub icon_theme: String,
+    pub icon_size: u32,
+    pub icon_fallback: String,
+
+    pub bar_height: f32,
+    pub slider_rail_width: f32,
+    pub slider_handle_radius: f32,
+    pub slider_handle_border_width: f32,
+    pub rule_radius: f32,
+
+    pub button: HashMap<String, ComputedStyleOverride>,
+    pub checkbox: HashMap<String, ComputedStyleOverride>,
+    pub text_input: HashMap<String, ComputedStyleOverride>,
+    pub picklist: HashMap<String, ComputedStyleOverride>,
+
+    /// Where the colors came from, see [`ImportConfig::watcher`]
+    pub import: Option<ImportConfig>,
+}
+
+/// How far an element is raised above its background
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Elevation {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+/// Size class of a component, selects its radius and border width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentSize {
+    Control,
+    Input,
+    Container,
+    Window,
+    /// Fully rounded ends, uses the border width of controls
+    Pill,
+}
+
+/// Radius large enough to be clamped to half the element height by the renderer
+pub const PILL_RADIUS: f32 = 9999.0;
+
+/// Kind of transition, selects the duration and easing of an animation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    /// State changes like toggling or selecting
+    Transition,
+    /// Slow fades like hover highlights
+    Fade,
+}
+
+impl ComputedOxiTheme {
+    /// Applies a color transformation to every color of the theme
+    pub fn map_colors(self, map: impl Fn(&Color) -> Color) -> Self {
+        let map_shadow = |shadow: Shadow| Shadow {
+            color: map(&shadow.color),
+            ..shadow
+        };
+        let map_overrides = |overrides: HashMap<String, ComputedStyleOverride>| {
+            overrides
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = ComputedStyleOverride {
+                        background: value.background.as_ref().map(&map),
+                        text: value.text.as_ref().map(&map),
+                        border_color: value.border_color.as_ref().map(&map),
+                        ..value
+                    };
+                    (key, value)
+                })
+                .collect()
+        };
+        Self {
+            base: map(&self.base),
+            mantle: map(&self.mantle),
+            mantle_hover: map(&self.mantle_hover),
+            mantle_active: map(&self.mantle_active),
+            primary_bg: map(&self.primary_bg),
+            primary_bg_hover: map(&self.primary_bg_hover),
+            primary_bg_active: map(&self.primary_bg_active),
+            secondary_bg: map(&self.secondary_bg),
+            secondary_bg_hover: map(&self.secondary_bg_hover),
+            secondary_bg_active: map(&self.secondary_bg_active),
+            tertiary_bg: map(&self.tertiary_bg),
+            tertiary_bg_hover: map(&self.tertiary_bg_hover),
+            tertiary_bg_active: map(&self.tertiary_bg_active),
+            text: map(&self.text),
+            text_muted: map(&self.text_muted),
+            tint: map(&self.tint),
+            shade: map(&self.shade),
+            primary: map(&self.primary),
+            primary_hover: map(&self.primary_hover),
+            primary_active: map(&self.primary_active),
+            secondary: map(&self.secondary),
+            secondary_hover: map(&self.secondary_hover),
+            secondary_active: map(&self.secondary_active),
+            primary_contrast: map(&self.primary_contrast),
+            secondary_contrast: map(&self.secondary_contrast),
+            good: map(&self.good),
+            good_hover: map(&self.good_hover),
+            good_active: map(&self.good_active),
+            good_contrast: map(&self.good_contrast),
+            bad: map(&self.bad),
+            bad_hover: map(&self.bad_hover),
+            bad_active: map(&self.bad_active),
+            bad_contrast: map(&self.bad_contrast),
+            info: map(&self.info),
+            info_hover: map(&self.info_hover),
+            info_active: map(&self.info_active),
+            info_contrast: map(&self.info_contrast),
+            warning: map(&self.warning),
+            warning_hover: map(&self.warning_hover),
+            warning_active: map(&self.warning_active),
+            warning_contrast: map(&self.warning_contrast),
+            rose: map(&self.rose),
+            lavender: map(&self.lavender),
+            blue: map(&self.blue),
+            mauve: map(&self.mauve),
+            flamingo: map(&self.flamingo),
+            border_color_weak: map(&self.border_color_weak),
+            border_color_strong: map(&self.border_color_strong),
+            shadow_low: map_shadow(self.shadow_low),
+            shadow_medium: map_shadow(self.shadow_medium),
+            shadow_high: map_shadow(self.shadow_high),
+            button: map_overrides(self.button),
+            checkbox: map_overrides(self.checkbox),
+            text_input: map_overrides(self.text_input),
+            picklist: map_overrides(self.picklist),
+            ..self
+        }
+    }
+
+    /// Returns the theme as it is perceived with the given color vision deficiency
+    pub fn simulate(&self, deficiency: ColorVisionDeficiency) -> Self {
+        self.clone().map_colors(|color| simulate(color, deficiency))
+    }
+
+    /// Semantic color pairs that can no longer be told apart
+    pub fn indistinguishable_pairs(&self) -> Vec<(&'static str, &'static str)> {
+        indistinguishable_pairs(&[
+            ("primary", self.primary),
+            ("secondary", self.secondary),
+            ("good", self.good),
+            ("bad", self.bad),
+            ("info", self.info),
+            ("warning", self.warning),
+        ])
+    }
+
+    /// Backgrounds that text is drawn on, in every interaction state.
+    /// Tertiary backgrounds only fill controls like unchecked checkboxes.
+    pub fn text_backgrounds(&self) -> [Color; 10] {
+        [
+            self.base,
+            self.mantle,
+            self.mantle_hover,
+            self.mantle_active,
+            self.primary_bg,
+            self.primary_bg_hover,
+            self.primary_bg_active,
+            self.secondary_bg,
+            self.secondary_bg_hover,
+            self.secondary_bg_active,
+        ]
+    }
+
+    /// Raises all text to AAA contrast on every background and state it is drawn on,
+    /// uses strong borders everywhere and removes translucency.
+    /// Muted text only reaches AA so it stays distinct from regular text.
+    fn with_high_contrast(self) -> Self {
+        let backgrounds = self.text_backgrounds();
+        let border = ensure_contrast_on_all(&self.border_color_strong, &backgrounds, CONTRAST_AAA);
+        let on_accent = |contrast: &Color, base: Color, hover: Color, active: Color| {
+            ensure_contrast_on_all(contrast, &[base, hover, active], CONTRAST_AAA)
+        };
+        Self {
+            text: ensure_contrast_on_all(&self.text, &backgrounds, CONTRAST_AAA),
+            text_muted: ensure_contrast_on_all(&self.text_muted, &backgrounds, CONTRAST_AA),
+            primary_contrast: on_accent(
+                &self.primary_contrast,
+                self.primary,
+                self.primary_hover,
+                self.primary_active,
+            ),
+            secondary_contrast: on_accent(
+                &self.secondary_contrast,
+                self.secondary,
+                self.secondary_hover,
+                self.secondary_active,
+            ),
+            good_contrast: on_accent(
+                &self.good_contrast,
+                self.good,
+                self.good_hover,
+                self.good_active,
+            ),
+            bad_contrast: on_accent(
+                &self.bad_contrast,
+                self.bad,
+                self.bad_hover,
+                self.bad_active,
+            ),
+            info_contrast: on_accent(
+                &self.info_contrast,
+                self.info,
+                self.info_hover,
+                self.info_active,
+            ),
+            warning_contrast: on_accent(
+                &self.warning_contrast,
+                self.warning,
+                self.warning_hover,
+                self.warning_active,
+            ),
+            border_color_weak: border,
+            border_color_strong: border,
+            border_width_control: self
+                .border_width_control
+                .max(self.high_contrast_border_width),
+            border_width_input: self.border_width_input.max(self.high_contrast_border_width),
+            border_width_container: self
+                .border_width_container
+                .max(self.high_contrast_border_width),
+            border_width_window: self
+                .border_width_window
+                .max(self.high_contrast_border_width),
+            shadows_enabled: false,
+            shadow_low: Shadow::default(),
+            shadow_medium: Shadow::default(),
+            shadow_high: Shadow::default(),
+            ..self
+        }
+    }
+
+    /// Creates an animated value with the duration and easing of the theme.
+    /// Transitions are instant when reduced motion is enabled.
+    pub fn animated<T>(&self, value: T, motion: Motion) -> Animated<T, Instant>
+    where
+        T: FloatRepresentable + Clone + Copy + PartialEq,
+    {
+        let (duration, easing) = match motion {
+            Motion::Transition => (self.animation_duration, self.animation_easing),
+            Motion::Fade => (self.animation_duration_slow, self.animation_easing_fade),
+        };
+        let duration = if self.reduced_motion { 0.0 } else { duration };
+        Animated::new_with_settings(value, duration, easing)
+    }
+
+    pub fn radius(&self, size: ComponentSize) -> Radius {
+        let radius = match size {
+            ComponentSize::Control => self.radius_control,
+            ComponentSize::Input => self.radius_input,
+            ComponentSize::Container => self.radius_container,
+            ComponentSize::Window => self.radius_window,
+            ComponentSize::Pill => PILL_RADIUS,
+        };
+        Radius::from(radius)
+    }
+
+    pub fn border_width(&self, size: ComponentSize) -> f32 {
+        match size {
+            ComponentSize::Control | ComponentSize::Pill => self.border_width_control,
+            ComponentSize::Input => self.border_width_input,
+            ComponentSize::Container => self.border_width_container,
+            ComponentSize::Window => self.border_width_window,
+        }
+    }
+
+    pub fn shadow(&self, elevation: Elevation) -> Shadow {
+        match elevation {
+            Elevation::None => Shadow::default(),
+            Elevation::Low => self.shadow_low,
+            Elevation::Medium => self.shadow_medium,
+            Elevation::High => self.shadow_high,
+        }
+    }
+}
+
+fn mk_shadow(enabled: bool, color: &str, offset: f32, blur_radius: f32) -> Shadow {
+    if !enabled {
+        return Shadow::default();
+    }
+    Shadow {
+        color: mk_color(color),
+        offset: Vector::new(0.0, offset),
+        blur_radius,
+    }
+}
+
+#[test]
+fn test_high_contrast_text_is_readable_everywhere() {
+    use crate::{
+        utils::color::{DISTINGUISHABLE_DIFFERENCE, color_difference, contrast_ratio},
+        widgets::common::Variant,
+    };
+
+    let theme = ComputedOxiTheme::from(OxiTheme {
+        high_contrast: true,
+        ..default_theme()
+    });
+    for background in theme.text_backgrounds() {
+        assert!(contrast_ratio(&theme.text, &background) >= CONTRAST_AAA);
+        assert!(contrast_ratio(&theme.text_muted, &background) >= CONTRAST_AA);
+    }
+    assert!(color_difference(&theme.text, &theme.text_muted) >= DISTINGUISHABLE_DIFFERENCE);
+    for variant in [
+        Variant::Primary,
+        Variant::Success,
+        Variant::Danger,
+        Variant::Warning,
+    ] {
+        let colors = theme.variant(variant);
+        for background in [colors.base, colors.hover, colors.active] {
+            assert!(contrast_ratio(&colors.contrast, &background) >= CONTRAST_AAA);
+        }
+    }
 }
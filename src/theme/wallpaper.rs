@@ -0,0 +1,185 @@
+use std::path::Path;
+
+use image::{ImageError, imageops::FilterType};
+
+use crate::{
+    theme::theme_impl::OxiTheme,
+    utils::color::{from_oklab, to_oklab},
+};
+
+/// Wallpapers are scaled down to this size before clustering, more pixels do not change the result
+const SAMPLE_SIZE: u32 = 128;
+const CLUSTER_COUNT: usize = 8;
+const MAX_ITERATIONS: usize = 20;
+/// Clusters covering less of the image are treated as noise
+const MIN_CLUSTER_WEIGHT: f32 = 0.02;
+/// Minimal hue distance in degrees for a second accent to become the secondary color
+const SECONDARY_HUE_DISTANCE: f32 = 30.0;
+/// Wallpapers with a dominant color darker than this get a dark theme
+const DARK_THRESHOLD: f32 = 0.6;
+
+/// A color cluster in OKLab and the share of pixels it covers
+#[derive(Debug, Clone, Copy)]
+struct Cluster {
+    lab: [f32; 3],
+    weight: f32,
+}
+
+impl Cluster {
+    fn chroma(&self) -> f32 {
+        self.lab[1].hypot(self.lab[2])
+    }
+
+    fn hue(&self) -> f32 {
+        self.lab[2]
+            .atan2(self.lab[1])
+            .to_degrees()
+            .rem_euclid(360.0)
+    }
+}
+
+impl OxiTheme {
+    /// Generates a theme from a local wallpaper, similar to pywal or matugen.
+    ///
+    /// The image is clustered with k-means in OKLab, the most colorful cluster becomes the seed
+    /// for [`OxiTheme::from_seed`] and a second distinct accent becomes the secondary color.
+    /// Without `dark` the variant follows the lightness of the dominant color.
+    pub fn from_wallpaper(path: &Path, dark: Option<bool>) -> Result<OxiTheme, ImageError> {
+        let image = image::open(path)?
+            .resize(SAMPLE_SIZE, SAMPLE_SIZE, FilterType::Triangle)
+            .into_rgba8();
+        let pixels: Vec<[f32; 3]> = image
+            .pixels()
+            .filter(|pixel| pixel.0[3] > 0)
+            .map(|pixel| {
+                let [r, g, b, _] = pixel.0;
+                to_oklab(&iced::Color::from_rgb8(r, g, b))
+            })
+            .collect();
+        Ok(theme_from_pixels(&pixels, dark))
+    }
+}
+
+fn theme_from_pixels(pixels: &[[f32; 3]], dark: Option<bool>) -> OxiTheme {
+    let mut clusters = kmeans(pixels, CLUSTER_COUNT);
+    clusters.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    let Some(dominant) = clusters.first().copied() else {
+        return OxiTheme::from_seed(iced::Color::BLACK, dark.unwrap_or(true));
+    };
+    let dark = dark.unwrap_or(dominant.lab[0] < DARK_THRESHOLD);
+
+    let mut accents: Vec<Cluster> = clusters
+        .into_iter()
+        .filter(|cluster| cluster.weight >= MIN_CLUSTER_WEIGHT)
+        .collect();
+    // colorful clusters win, but a tiny speck should not outweigh a large area
+    accents.sort_by(|a, b| {
+        let score = |cluster: &Cluster| cluster.chroma() * cluster.weight.sqrt();
+        score(b).total_cmp(&score(a))
+    });
+    let primary = accents.first().copied().unwrap_or(dominant);
+
+    let mut theme = OxiTheme::from_seed(from_oklab(primary.lab), dark);
+    let secondary = accents
+        .iter()
+        .find(|cluster| hue_distance(cluster.hue(), primary.hue()) >= SECONDARY_HUE_DISTANCE);
+    if let Some(secondary) = secondary {
+        let secondary_theme = OxiTheme::from_seed(from_oklab(secondary.lab), dark);
+        theme.secondary = secondary_theme.primary;
+        theme.secondary_contrast = secondary_theme.primary_contrast;
+    }
+    theme
+}
+
+fn hue_distance(a: f32, b: f32) -> f32 {
+    let distance = (a - b).abs() % 360.0;
+    distance.min(360.0 - distance)
+}
+
+/// Plain k-means, deterministically seeded with pixels spread over the lightness range
+fn kmeans(pixels: &[[f32; 3]], count: usize) -> Vec<Cluster> {
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted = pixels.to_vec();
+    sorted.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    let count = count.min(sorted.len());
+    let mut centers: Vec<[f32; 3]> = (0..count)
+        .map(|index| sorted[(2 * index + 1) * sorted.len() / (2 * count)])
+        .collect();
+
+    let mut assignments = vec![0; pixels.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (pixel, assignment) in pixels.iter().zip(assignments.iter_mut()) {
+            let nearest = nearest_center(pixel, &centers);
+            changed |= nearest != *assignment;
+            *assignment = nearest;
+        }
+
+        let mut sums = vec![([0.0; 3], 0usize); count];
+        for (pixel, assignment) in pixels.iter().zip(&assignments) {
+            let (sum, members) = &mut sums[*assignment];
+            sum.iter_mut()
+                .zip(pixel)
+                .for_each(|(sum, value)| *sum += value);
+            *members += 1;
+        }
+        for (center, (sum, members)) in centers.iter_mut().zip(&sums) {
+            if *members > 0 {
+                *center = sum.map(|value| value / *members as f32);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut members = vec![0usize; count];
+    assignments
+        .iter()
+        .for_each(|assignment| members[*assignment] += 1);
+    centers
+        .into_iter()
+        .zip(members)
+        .filter(|(_, members)| *members > 0)
+        .map(|(lab, members)| Cluster {
+            lab,
+            weight: members as f32 / pixels.len() as f32,
+        })
+        .collect()
+}
+
+fn nearest_center(pixel: &[f32; 3], centers: &[[f32; 3]]) -> usize {
+    let distance = |center: &[f32; 3]| {
+        center
+            .iter()
+            .zip(pixel)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f32>()
+    };
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_wallpaper_picks_colorful_accent() {
+    use crate::utils::color::{mk_color, to_oklch};
+
+    // mostly dark gray with an orange highlight
+    let gray = to_oklab(&mk_color("202020"));
+    let orange = to_oklab(&mk_color("ff8800"));
+    let mut pixels = vec![gray; 900];
+    pixels.extend(vec![orange; 100]);
+
+    let theme = theme_from_pixels(&pixels, None);
+    let [_, chroma, hue] = to_oklch(&mk_color(&theme.primary));
+    assert!(chroma > 0.08);
+    assert!(hue_distance(hue, to_oklch(&mk_color("ff8800"))[2]) < 10.0);
+    // dark wallpaper, dark theme
+    assert!(to_oklch(&mk_color(&theme.mantle))[0] < 0.5);
+}
@@ -0,0 +1,73 @@
+use std::{env, path::PathBuf, process};
+
+use serde::Serialize;
+
+use crate::{
+    theme::import::{ColorSource, ImportConfig},
+    utils::file::write_theme_toml,
+};
+
+/// theme.toml that only contains the `[import]` table, so apps import the source on startup
+#[derive(Serialize)]
+struct FollowingTheme {
+    import: ImportConfig,
+}
+
+/// `oxiced import <pywal|wallust|matugen> [path] [dark|light] [--follow]`
+///
+/// Writes the imported colors to theme.toml, with `--follow` only the `[import]` table is
+/// written and the colors are re-imported whenever an app starts or the source changes.
+pub fn import() -> Result<(), iced::Error> {
+    let args: Vec<String> = env::args().skip(2).collect();
+    let source = match args.first().map(String::as_str) {
+        Some("pywal") => ColorSource::Pywal,
+        Some("wallust") => ColorSource::Wallust,
+        Some("matugen") => ColorSource::Matugen,
+        _ => {
+            eprintln!(
+                "usage: oxiced import <pywal|wallust|matugen> [path] [dark|light] [--follow]"
+            );
+            process::exit(1);
+        }
+    };
+    let mut config = ImportConfig {
+        source,
+        path: None,
+        dark: None,
+        watch: None,
+    };
+    let mut follow = false;
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "--follow" => {
+                follow = true;
+                config.watch = Some(true);
+            }
+            "dark" => config.dark = Some(true),
+            "light" => config.dark = Some(false),
+            path => config.path = Some(PathBuf::from(path)),
+        }
+    }
+
+    let theme = match config.import() {
+        Ok(theme) => theme,
+        Err(error) => {
+            eprintln!("Could not import {source:?} colors: {error}");
+            process::exit(1);
+        }
+    };
+    let contents = if follow {
+        toml::to_string(&FollowingTheme { import: config })
+    } else {
+        toml::to_string(&theme)
+    }
+    .expect("theme can always be serialized");
+    match write_theme_toml(&contents) {
+        Ok(theme_path) => println!("Wrote {}", theme_path.display()),
+        Err(error) => {
+            eprintln!("Could not write theme.toml: {error}");
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use std::process;
+
+use crate::theme::migration::{CURRENT_VERSION, rewrite_theme_toml};
+
+/// `oxiced migrate` upgrades theme.toml to the current schema version,
+/// themes in the other formats are not versioned and left alone
+pub fn migrate() -> Result<(), iced::Error> {
+    match rewrite_theme_toml() {
+        Ok(Some(theme_path)) => println!(
+            "Migrated {} to version {CURRENT_VERSION}, the old file is kept as theme.toml.bak",
+            theme_path.display()
+        ),
+        Ok(None) => println!("theme.toml needs no migration"),
+        Err(error) => {
+            eprintln!("Could not migrate theme.toml: {error}");
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
@@ -1,2 +1,5 @@
+pub mod import;
+pub mod migrate;
 pub mod palette;
 pub mod test_app;
+pub mod wallpaper;
@@ -8,8 +8,15 @@ use iced::{
     widget::canvas::{self, Frame, Geometry},
 };
 use oxiced::{
-    theme::legacy_theme::{ExtendedHex, TomlBase16, parse_extended_palette},
-    utils::file::open_file,
+    theme::{
+        format::parse_theme,
+        legacy_theme::{ExtendedHex, TomlBase16, parse_extended_palette},
+        theme_impl::ComputedOxiTheme,
+    },
+    utils::{
+        color_vision::{ColorVisionDeficiency, simulate},
+        file::open_file,
+    },
 };
 
 pub fn palette() -> Result<(), iced::Error> {
@@ -19,6 +26,8 @@ pub fn palette() -> Result<(), iced::Error> {
 struct ThemeDisplay {
     theme: Extended,
     hex: ExtendedHex<'static>,
+    simulation: Option<ColorVisionDeficiency>,
+    indistinguishable: Vec<(&'static str, &'static str)>,
     canvas_cache: canvas::Cache,
 }
 
@@ -33,8 +42,13 @@ impl ThemeDisplay {
     fn new() -> (Self, Task<Message>) {
         let args: Vec<String> = env::args().collect();
         if args.len() < 3 {
-            panic!("Enter a path to a toml");
+            panic!("Enter a path to a toml and optionally a color vision deficiency to simulate");
         }
+        let simulation = args.get(3).map(|deficiency| {
+            deficiency
+                .parse::<ColorVisionDeficiency>()
+                .unwrap_or_else(|error| panic!("{}", error))
+        });
         let theme_path = args[2].clone();
         let file_res = open_file(PathBuf::from(theme_path));
         if let Err(error) = file_res {
@@ -48,6 +62,16 @@ impl ThemeDisplay {
         let theme = theme_opt.unwrap();
         println!("Parsed the following theme: {}", &theme);
         let palette = *theme.extended_palette();
+        // checked on the theme that apps render, not on the base16 palette shown below
+        let computed = ComputedOxiTheme::from(parse_theme(&file).unwrap_or_else(|error| {
+            panic!("{}", error);
+        }));
+        let indistinguishable = simulation
+            .map(|deficiency| computed.simulate(deficiency).indistinguishable_pairs())
+            .unwrap_or_default();
+        for (first, second) in &indistinguishable {
+            println!("{first} and {second} are hard to tell apart");
+        }
 
         let raw_theme_opt: Result<TomlBase16, _> = toml::from_str(&file);
         if let Err(error) = raw_theme_opt {
@@ -59,6 +83,8 @@ impl ThemeDisplay {
             Self {
                 theme: palette,
                 hex,
+                simulation,
+                indistinguishable,
                 canvas_cache: canvas::Cache::default(),
             },
             Task::none(),
@@ -251,6 +277,31 @@ impl ThemeDisplay {
                 },
                 *color,
             );
+            if let Some(deficiency) = self.simulation {
+                frame.fill_rectangle(
+                    Point {
+                        x: 410.0 + col as f32 * 600.0,
+                        y: (i as i32 + 1 - (col * count)) as f32 * 50.0,
+                    },
+                    Size {
+                        width: 50.0,
+                        height: 50.0,
+                    },
+                    simulate(color, deficiency),
+                );
+            }
+        }
+
+        for (i, (first, second)) in self.indistinguishable.iter().enumerate() {
+            frame.fill_text(canvas::Text {
+                content: format!("{first} and {second} are hard to tell apart"),
+                position: Point {
+                    x: 50.0,
+                    y: (count + 2 + i as i32) as f32 * 50.0,
+                },
+                color: Color::from_rgb(1.0, 0.3, 0.3),
+                ..canvas::Text::default()
+            });
         }
     }
 
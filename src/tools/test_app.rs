@@ -1,20 +1,31 @@
 use crate::theme::legacy_theme::get_all_themes;
-use crate::theme::theme_impl::get_derived_iced_theme;
-use crate::widgets::oxi_button::{ButtonVariant, button};
+use crate::theme::theme_impl::{OXITHEME, get_derived_iced_theme};
+use crate::utils::color_vision::simulation_from_env;
+use crate::widgets::common::Variant;
+use crate::widgets::oxi_bar::{Priority, Section, bar};
+use crate::widgets::oxi_button::{ButtonKind, button, kind_button, loading_button};
 use crate::widgets::oxi_card::Card;
 use crate::widgets::oxi_checkbox::checkbox;
+use crate::widgets::oxi_menu_button::{menu_button, split_button};
 use crate::widgets::oxi_picklist::pick_list;
 use crate::widgets::oxi_progress::progress_bar;
 use crate::widgets::oxi_radio::OxiRadio;
 // use crate::widgets::oxi_radio::radio;
 use crate::widgets::oxi_rule::{horizontal_rule, vertical_rule};
+use crate::widgets::oxi_segmented::segmented_control;
 use crate::widgets::oxi_slider::slider;
 use crate::widgets::oxi_text_input::text_input;
 use crate::widgets::oxi_toggler::OxiToggler;
-use iced::widget::{Column, column, text};
+use iced::widget::{Column, column, row, text};
 use iced::{Alignment, Length, Theme};
 
 pub fn test_app() -> iced::Result {
+    if let Some(deficiency) = simulation_from_env() {
+        println!("Simulating {deficiency:?}");
+        for (first, second) in OXITHEME.indistinguishable_pairs() {
+            println!("{first} and {second} are hard to tell apart");
+        }
+    }
     iced::application(Counter::default, Counter::update, Counter::view)
         .theme(Counter::theme)
         .run()
@@ -25,6 +36,7 @@ struct Counter {
     theme: Theme,
     is_checked: bool,
     is_toggled: bool,
+    is_expanded: bool,
     text: String,
 }
 
@@ -35,6 +47,7 @@ impl Default for Counter {
             theme: get_derived_iced_theme(),
             is_checked: false,
             is_toggled: false,
+            is_expanded: false,
             text: "".into(),
         }
     }
@@ -48,6 +61,7 @@ enum Message {
     TextChanged(String),
     Check(),
     Toggle(bool),
+    Expand(bool),
     Theme(Theme),
 }
 
@@ -69,6 +83,7 @@ impl Counter {
                 self.text += "1";
             }
             Message::TextChanged(val) => self.text = val,
+            Message::Expand(val) => self.is_expanded = val,
             Message::Set(val) => {
                 self.value = val;
             }
@@ -91,12 +106,52 @@ impl Counter {
 
 fn counter_box<'a>(state: &Counter) -> Column<'a, Message> {
     column![
-        button("Increment", ButtonVariant::Primary).on_press(Message::Increment(10)),
+        bar()
+            .start(text("start"))
+            .push(Section::Start, Priority::Low, text("hidden first"))
+            .center(text(state.value))
+            .end(text("end")),
+        button("Increment", Variant::Primary).on_press(Message::Increment(10)),
         text(state.value).size(50),
-        button("Decrement", ButtonVariant::Secondary).on_press(Message::Decrement(20)),
-        button("success", ButtonVariant::Success).on_press(Message::Increment(10)),
-        button("danger", ButtonVariant::Danger).on_press(Message::Increment(10)),
-        button("row", ButtonVariant::PrimaryBg).on_press(Message::Increment(10)),
+        button("Decrement", Variant::Secondary).on_press(Message::Decrement(20)),
+        button("success", Variant::Success).on_press(Message::Increment(10)),
+        button("danger", Variant::Danger).on_press(Message::Increment(10)),
+        button("row", Variant::Surface).on_press(Message::Increment(10)),
+        row![
+            kind_button("tonal", Variant::Primary, ButtonKind::Tonal)
+                .on_press(Message::Increment(1)),
+            kind_button("outlined", Variant::Warning, ButtonKind::Outlined)
+                .on_press(Message::Increment(1)),
+            kind_button("ghost", Variant::Info, ButtonKind::Ghost).on_press(Message::Increment(1)),
+            kind_button("link", Variant::Primary, ButtonKind::Link).on_press(Message::Increment(1)),
+            loading_button(
+                "connect",
+                Variant::Primary,
+                ButtonKind::Filled,
+                state.is_toggled,
+                Message::Increment(1)
+            ),
+        ]
+        .spacing(OXITHEME.padding_md),
+        row![
+            split_button(
+                "save",
+                Message::Increment(1),
+                [10, 20, 30],
+                Message::Set,
+                Variant::Primary,
+                ButtonKind::Filled
+            ),
+            menu_button(
+                "set to",
+                [10, 20, 30],
+                Message::Set,
+                Variant::Neutral,
+                ButtonKind::Tonal
+            ),
+        ]
+        .spacing(OXITHEME.padding_md),
+        segmented_control([10, 20, 30], Some(state.value), Message::Set),
         checkbox("what", state.is_checked, |_| { Message::Check() }),
         OxiRadio::new(Some("testeroni"), Some(state.value), 10, Some(Message::Set)),
         // radio("first", 10, Some(state.value), Message::Set),
@@ -111,6 +166,11 @@ fn counter_box<'a>(state: &Counter) -> Column<'a, Message> {
             String::from("test"),
             text_input("something", state.text.as_str(), Message::TextChanged).into(),
         ),
+        Card::new(text(state.text.clone()))
+            .title("details")
+            .subtitle("expandable")
+            .action(button("reset", Variant::Secondary).on_press(Message::Set(0)))
+            .expandable(state.is_expanded, Message::Expand),
         horizontal_rule(10),
         vertical_rule(10),
     ]
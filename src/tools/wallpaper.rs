@@ -0,0 +1,33 @@
+use std::{env, path::PathBuf, process};
+
+use crate::{theme::theme_impl::OxiTheme, utils::file::write_theme_toml};
+
+/// `oxiced wallpaper <image> [dark|light]` generates a theme and writes it to theme.toml
+pub fn wallpaper() -> Result<(), iced::Error> {
+    let args: Vec<String> = env::args().collect();
+    let Some(path) = args.get(2).map(PathBuf::from) else {
+        eprintln!("usage: oxiced wallpaper <image> [dark|light]");
+        process::exit(1);
+    };
+    let dark = match args.get(3).map(String::as_str) {
+        Some("dark") => Some(true),
+        Some("light") => Some(false),
+        _ => None,
+    };
+    let theme = match OxiTheme::from_wallpaper(&path, dark) {
+        Ok(theme) => theme,
+        Err(error) => {
+            eprintln!("Could not read {}: {error}", path.display());
+            process::exit(1);
+        }
+    };
+    let contents = toml::to_string(&theme).expect("theme can always be serialized");
+    match write_theme_toml(&contents) {
+        Ok(theme_path) => println!("Wrote {}", theme_path.display()),
+        Err(error) => {
+            eprintln!("Could not write theme.toml: {error}");
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use iced::{Color, color};
 
 pub fn mk_color(color: &str) -> Color {
+    if color.len() == 8 {
+        let rgba = u32::from_str_radix(color, 16).unwrap_or(0);
+        return color!(rgba >> 8, (rgba & 0xff) as f32 / 255.0);
+    }
     color!(u32::from_str_radix(color, 16).unwrap_or(0) as f32)
 }
 
@@ -29,3 +33,198 @@ pub fn darken_color(color: &Color, amount: f32) -> Color {
         ..*color
     }
 }
+
+/// Environment variable that enables the high contrast mode when set to anything but 0 or false
+pub const HIGH_CONTRAST_ENV: &str = "OXICED_HIGH_CONTRAST";
+
+pub fn high_contrast_from_env() -> bool {
+    std::env::var(HIGH_CONTRAST_ENV).is_ok_and(|value| value != "0" && value != "false")
+}
+
+/// Minimum contrast ratio for normal text according to WCAG AAA
+pub const CONTRAST_AAA: f32 = 7.0;
+/// Minimum contrast ratio for normal text according to WCAG AA
+pub const CONTRAST_AA: f32 = 4.5;
+
+/// Relative luminance as defined by WCAG 2
+pub fn relative_luminance(color: &Color) -> f32 {
+    let [r, g, b, _] = color.into_linear();
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Contrast ratio between two colors as defined by WCAG 2, ranges from 1 to 21
+pub fn contrast_ratio(a: &Color, b: &Color) -> f32 {
+    let a = relative_luminance(a);
+    let b = relative_luminance(b);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Moves the foreground towards black or white until it reaches the given contrast ratio
+pub fn ensure_contrast(foreground: &Color, background: &Color, ratio: f32) -> Color {
+    if contrast_ratio(foreground, background) >= ratio {
+        return Color {
+            a: 1.0,
+            ..*foreground
+        };
+    }
+    let target = readable_on(background);
+    let mix = |factor: f32| Color {
+        r: foreground.r + (target.r - foreground.r) * factor,
+        g: foreground.g + (target.g - foreground.g) * factor,
+        b: foreground.b + (target.b - foreground.b) * factor,
+        a: 1.0,
+    };
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..16 {
+        let middle = (low + high) / 2.0;
+        if contrast_ratio(&mix(middle), background) >= ratio {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    mix(high)
+}
+
+/// Like [`ensure_contrast`], but readable on every one of the backgrounds
+pub fn ensure_contrast_on_all(foreground: &Color, backgrounds: &[Color], ratio: f32) -> Color {
+    // a second pass catches backgrounds that an adjustment for a later one made worse
+    (0..2).fold(*foreground, |foreground, _| {
+        backgrounds
+            .iter()
+            .fold(foreground, |foreground, background| {
+                ensure_contrast(&foreground, background, ratio)
+            })
+    })
+}
+
+/// Converts a color to the perceptual OKLab color space as [lightness, a, b]
+pub fn to_oklab(color: &Color) -> [f32; 3] {
+    let [r, g, b, _] = color.into_linear();
+    let l = (0.41222147 * r + 0.53633254 * g + 0.05144599 * b).cbrt();
+    let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+    let s = (0.08830246 * r + 0.28171884 * g + 0.6299787 * b).cbrt();
+    [
+        0.21045426 * l + 0.7936178 * m - 0.00407205 * s,
+        1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+        0.02590404 * l + 0.78277177 * m - 0.80867577 * s,
+    ]
+}
+
+/// Converts a color from the OKLab color space, the result may be outside of sRGB
+pub fn from_oklab(lab: [f32; 3]) -> Color {
+    let [lightness, a, b] = lab;
+    let l = (lightness + 0.39633778 * a + 0.21580376 * b).powi(3);
+    let m = (lightness - 0.105561346 * a - 0.06385417 * b).powi(3);
+    let s = (lightness - 0.08948418 * a - 1.2914855 * b).powi(3);
+    // Color::from_linear_rgba asserts the range, out of gamut values are valid here
+    let gamma = |u: f32| {
+        if u < 0.0031308 {
+            12.92 * u
+        } else {
+            1.055 * u.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    Color {
+        r: gamma(4.0767417 * l - 3.3077116 * m + 0.23096994 * s),
+        g: gamma(-1.268438 * l + 2.6097574 * m - 0.34131938 * s),
+        b: gamma(-0.0041960864 * l - 0.7034186 * m + 1.7076147 * s),
+        a: 1.0,
+    }
+}
+
+/// Converts a color to the cylindrical OKLCH space as [lightness, chroma, hue in degrees]
+pub fn to_oklch(color: &Color) -> [f32; 3] {
+    let [lightness, a, b] = to_oklab(color);
+    let hue = b.atan2(a).to_degrees().rem_euclid(360.0);
+    [lightness, a.hypot(b), hue]
+}
+
+/// Converts an OKLCH color to sRGB, chroma is reduced until the color fits into sRGB
+pub fn from_oklch(lightness: f32, chroma: f32, hue: f32) -> Color {
+    let lab = |chroma: f32| {
+        let hue = hue.to_radians();
+        from_oklab([lightness, chroma * hue.cos(), chroma * hue.sin()])
+    };
+    let in_gamut = |color: &Color| {
+        [color.r, color.g, color.b]
+            .iter()
+            .all(|channel| (-0.001..=1.001).contains(channel))
+    };
+    let color = lab(chroma);
+    if in_gamut(&color) {
+        return clamp_color(&color);
+    }
+    let (mut low, mut high) = (0.0, chroma);
+    for _ in 0..16 {
+        let middle = (low + high) / 2.0;
+        if in_gamut(&lab(middle)) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    clamp_color(&lab(low))
+}
+
+fn clamp_color(color: &Color) -> Color {
+    Color {
+        r: color.r.clamp(0.0, 1.0),
+        g: color.g.clamp(0.0, 1.0),
+        b: color.b.clamp(0.0, 1.0),
+        a: color.a.clamp(0.0, 1.0),
+    }
+}
+
+/// Black or white, whichever is more readable on the background
+pub fn readable_on(background: &Color) -> Color {
+    if contrast_ratio(&Color::BLACK, background) >= contrast_ratio(&Color::WHITE, background) {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+/// Hex representation as used in theme files: rrggbb
+pub fn to_hex(color: &Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("{r:02x}{g:02x}{b:02x}")
+}
+
+/// Mixes two colors in OKLab, factors outside of 0..1 extrapolate and are clamped to sRGB
+pub fn mix_oklab(a: &Color, b: &Color, factor: f32) -> Color {
+    let a = to_oklab(a);
+    let b = to_oklab(b);
+    clamp_color(&from_oklab(
+        [0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * factor),
+    ))
+}
+
+/// Minimum perceptual difference for semantic colors to be told apart at a glance
+pub const DISTINGUISHABLE_DIFFERENCE: f32 = 0.06;
+
+/// Perceptual distance between two colors, below roughly 0.02 they look identical
+pub fn color_difference(a: &Color, b: &Color) -> f32 {
+    let a = to_oklab(a);
+    let b = to_oklab(b);
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+#[test]
+fn test_contrast_ratio_extremes() {
+    assert!((contrast_ratio(&Color::BLACK, &Color::WHITE) - 21.0).abs() < 0.01);
+    assert!((contrast_ratio(&Color::WHITE, &Color::WHITE) - 1.0).abs() < 0.01);
+}
+
+#[test]
+fn test_ensure_contrast_reaches_aaa() {
+    let background = mk_color("f38ba8");
+    let foreground = ensure_contrast(&mk_color("ffffff"), &background, CONTRAST_AAA);
+    assert!(contrast_ratio(&foreground, &background) >= CONTRAST_AAA);
+}
+
+#[test]
+fn test_oklab_round_trip() {
+    let color = mk_color("89b4fa");
+    assert_eq!(to_hex(&from_oklab(to_oklab(&color))), "89b4fa");
+}
@@ -0,0 +1,117 @@
+use std::{env, str::FromStr};
+
+use iced::Color;
+
+use crate::utils::color::{DISTINGUISHABLE_DIFFERENCE, color_difference};
+
+/// Environment variable that renders every Oxi widget as seen with the given deficiency
+pub const SIMULATE_ENV: &str = "OXICED_SIMULATE";
+
+/// Color vision deficiencies that can be simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorVisionDeficiency {
+    /// Missing red cones
+    Protanopia,
+    /// Missing green cones
+    Deuteranopia,
+    /// Missing blue cones
+    Tritanopia,
+    /// No color perception at all
+    Achromatopsia,
+}
+
+impl ColorVisionDeficiency {
+    pub const ALL: [ColorVisionDeficiency; 4] = [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+        ColorVisionDeficiency::Tritanopia,
+        ColorVisionDeficiency::Achromatopsia,
+    ];
+
+    /// Simulation matrix in linear RGB
+    /// Machado, Oliveira and Fernandes 2009 with a severity of 1.0
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVisionDeficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVisionDeficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+            ColorVisionDeficiency::Achromatopsia => [[0.2126, 0.7152, 0.0722]; 3],
+        }
+    }
+}
+
+impl FromStr for ColorVisionDeficiency {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "protanopia" => Ok(ColorVisionDeficiency::Protanopia),
+            "deuteranopia" => Ok(ColorVisionDeficiency::Deuteranopia),
+            "tritanopia" => Ok(ColorVisionDeficiency::Tritanopia),
+            "achromatopsia" => Ok(ColorVisionDeficiency::Achromatopsia),
+            _ => Err(format!("unknown color vision deficiency: {value}")),
+        }
+    }
+}
+
+pub fn simulation_from_env() -> Option<ColorVisionDeficiency> {
+    env::var(SIMULATE_ENV).ok()?.parse().ok()
+}
+
+/// Transforms a color to how it is perceived with the given deficiency
+pub fn simulate(color: &Color, deficiency: ColorVisionDeficiency) -> Color {
+    let [r, g, b, a] = color.into_linear();
+    let [red, green, blue] = deficiency
+        .matrix()
+        .map(|row| (row[0] * r + row[1] * g + row[2] * b).clamp(0.0, 1.0));
+    Color::from_linear_rgba(red, green, blue, a)
+}
+
+/// Pairs of the named colors that can't be told apart at a glance,
+/// pass colors through [`simulate`] first to check them for a deficiency
+pub fn indistinguishable_pairs(
+    colors: &[(&'static str, Color)],
+) -> Vec<(&'static str, &'static str)> {
+    let mut pairs = Vec::new();
+    for (index, (name, color)) in colors.iter().enumerate() {
+        for (other_name, other_color) in &colors[index + 1..] {
+            if color_difference(color, other_color) < DISTINGUISHABLE_DIFFERENCE {
+                pairs.push((*name, *other_name));
+            }
+        }
+    }
+    pairs
+}
+
+#[test]
+fn test_achromatopsia_is_gray() {
+    let color = simulate(
+        &Color::from_rgb(0.9, 0.2, 0.4),
+        ColorVisionDeficiency::Achromatopsia,
+    );
+    assert!((color.r - color.g).abs() < 0.001);
+    assert!((color.g - color.b).abs() < 0.001);
+}
+
+#[test]
+fn test_gray_is_unchanged() {
+    let gray = Color::from_rgb(0.5, 0.5, 0.5);
+    for deficiency in ColorVisionDeficiency::ALL {
+        let color = simulate(&gray, deficiency);
+        assert!((color.r - gray.r).abs() < 0.01);
+        assert!((color.g - gray.g).abs() < 0.01);
+        assert!((color.b - gray.b).abs() < 0.01);
+    }
+}
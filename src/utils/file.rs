@@ -1,4 +1,8 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::PathBuf,
+};
 
 pub fn get_theme_toml() -> std::io::Result<String> {
     let config = xdg::BaseDirectories::with_prefix("oxiced");
@@ -12,6 +16,17 @@ pub fn get_theme_toml() -> std::io::Result<String> {
     open_file(theme_path.unwrap())
 }
 
+/// Writes theme.toml to the user config directory, an existing file is kept as theme.toml.bak
+pub fn write_theme_toml(contents: &str) -> std::io::Result<PathBuf> {
+    let config = xdg::BaseDirectories::with_prefix("oxiced");
+    let theme_path = config.place_config_file("theme.toml")?;
+    if theme_path.exists() {
+        fs::copy(&theme_path, theme_path.with_extension("toml.bak"))?;
+    }
+    fs::write(&theme_path, contents)?;
+    Ok(theme_path)
+}
+
 pub fn open_file(path: PathBuf) -> std::io::Result<String> {
     let mut theme_file = File::open(path)?;
     let mut theme_string = String::from("");
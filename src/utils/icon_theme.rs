@@ -0,0 +1,392 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{theme::theme_impl::OXITHEME, utils::file::open_file};
+
+/// Every icon theme inherits from hicolor, whether it says so or not
+const HICOLOR: &str = "hicolor";
+const SYMBOLIC_SUFFIX: &str = "-symbolic";
+
+/// Icon lookup of [`icon_widget`](crate::widgets::oxi_icon::icon_widget).
+///
+/// Only finds SVGs, as those are the only icons the svg widget can draw and recolor.
+pub static ICON_LOOKUP: Lazy<IconLookup> = Lazy::new(|| {
+    let theme = Some(OXITHEME.icon_theme.clone())
+        .filter(|theme| !theme.is_empty())
+        .or_else(gtk_icon_theme)
+        .unwrap_or_else(|| String::from(HICOLOR));
+    IconLookup::new(theme, icon_base_dirs())
+        .extensions(&["svg"])
+        .fallback(&OXITHEME.icon_fallback)
+});
+
+/// Directories that contain icon themes, in the order of the icon theme specification
+pub fn icon_base_dirs() -> Vec<PathBuf> {
+    let dirs = xdg::BaseDirectories::new();
+    let home_icons = std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".icons"));
+    home_icons
+        .into_iter()
+        .chain(dirs.get_data_home().map(|data| data.join("icons")))
+        .chain(
+            dirs.get_data_dirs()
+                .into_iter()
+                .map(|data| data.join("icons")),
+        )
+        .chain([PathBuf::from("/usr/share/pixmaps")])
+        .collect()
+}
+
+/// Icon theme configured for GTK, which most desktops keep in sync with their own setting
+fn gtk_icon_theme() -> Option<String> {
+    let dirs = xdg::BaseDirectories::new();
+    ["gtk-4.0/settings.ini", "gtk-3.0/settings.ini"]
+        .into_iter()
+        .filter_map(|file| dirs.find_config_file(file))
+        .filter_map(|path| open_file(path).ok())
+        .find_map(|settings| {
+            parse_ini(&settings)
+                .get("Settings")?
+                .get("gtk-icon-theme-name")
+                .cloned()
+        })
+}
+
+type Ini = HashMap<String, HashMap<String, String>>;
+
+/// Parses the desktop entry style files used by index.theme and the GTK settings
+fn parse_ini(contents: &str) -> Ini {
+    let mut sections = Ini::new();
+    let mut section = String::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = String::from(name);
+        } else if let Some((key, value)) = line.split_once('=') {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(String::from(key.trim()), String::from(value.trim()));
+        }
+    }
+    sections
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirectoryType {
+    Fixed,
+    Scalable,
+    Threshold,
+}
+
+/// Subdirectory of an icon theme such as `24x24/actions`
+#[derive(Debug, Clone)]
+struct Directory {
+    path: String,
+    size: u32,
+    scale: u32,
+    kind: DirectoryType,
+    min_size: u32,
+    max_size: u32,
+    threshold: u32,
+}
+
+impl Directory {
+    fn parse(path: &str, keys: &HashMap<String, String>) -> Option<Directory> {
+        let number = |key: &str| keys.get(key).and_then(|value| value.parse::<u32>().ok());
+        let size = number("Size")?;
+        let kind = match keys.get("Type").map(String::as_str) {
+            Some("Fixed") => DirectoryType::Fixed,
+            Some("Scalable") => DirectoryType::Scalable,
+            _ => DirectoryType::Threshold,
+        };
+        Some(Directory {
+            path: String::from(path),
+            size,
+            scale: number("Scale").unwrap_or(1),
+            kind,
+            min_size: number("MinSize").unwrap_or(size),
+            max_size: number("MaxSize").unwrap_or(size),
+            threshold: number("Threshold").unwrap_or(2),
+        })
+    }
+
+    fn matches_size(&self, size: u32, scale: u32) -> bool {
+        if self.scale != scale {
+            return false;
+        }
+        match self.kind {
+            DirectoryType::Fixed => self.size == size,
+            DirectoryType::Scalable => (self.min_size..=self.max_size).contains(&size),
+            DirectoryType::Threshold => (self.size.saturating_sub(self.threshold)
+                ..=self.size + self.threshold)
+                .contains(&size),
+        }
+    }
+
+    fn size_distance(&self, size: u32, scale: u32) -> u32 {
+        let wanted = size * scale;
+        let (min, max) = match self.kind {
+            DirectoryType::Fixed => (self.size, self.size),
+            DirectoryType::Scalable => (self.min_size, self.max_size),
+            DirectoryType::Threshold => (
+                self.size.saturating_sub(self.threshold),
+                self.size + self.threshold,
+            ),
+        };
+        if wanted < min * self.scale {
+            min * self.scale - wanted
+        } else {
+            wanted.saturating_sub(max * self.scale)
+        }
+    }
+}
+
+/// Parsed index.theme of an installed icon theme
+#[derive(Debug, Clone)]
+struct IconTheme {
+    inherits: Vec<String>,
+    directories: Vec<Directory>,
+    /// Every base directory that contains the theme, their contents are merged
+    roots: Vec<PathBuf>,
+}
+
+impl IconTheme {
+    fn load(name: &str, base_dirs: &[PathBuf]) -> Option<IconTheme> {
+        let roots: Vec<PathBuf> = base_dirs
+            .iter()
+            .map(|base| base.join(name))
+            .filter(|root| root.is_dir())
+            .collect();
+        let index = roots
+            .iter()
+            .find_map(|root| open_file(root.join("index.theme")).ok())?;
+        let index = parse_ini(&index);
+        let theme = index.get("Icon Theme")?;
+        let list = |key: &str| -> Vec<String> {
+            theme
+                .get(key)
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let directories = list("Directories")
+            .into_iter()
+            .chain(list("ScaledDirectories"))
+            .filter_map(|path| Directory::parse(&path, index.get(&path)?))
+            .collect();
+        Some(IconTheme {
+            inherits: list("Inherits"),
+            directories,
+            roots,
+        })
+    }
+
+    /// `LookupIcon` of the specification: an exact size match, otherwise the closest size
+    fn lookup(&self, icon: &str, size: u32, scale: u32, extensions: &[&str]) -> Option<PathBuf> {
+        let find = |directory: &Directory| {
+            self.roots.iter().find_map(|root| {
+                extensions.iter().find_map(|extension| {
+                    let path = root
+                        .join(&directory.path)
+                        .join(format!("{icon}.{extension}"));
+                    path.is_file().then_some(path)
+                })
+            })
+        };
+        let exact = self
+            .directories
+            .iter()
+            .filter(|directory| directory.matches_size(size, scale))
+            .find_map(find);
+        exact.or_else(|| {
+            self.directories
+                .iter()
+                .filter_map(|directory| {
+                    find(directory).map(|path| (directory.size_distance(size, scale), path))
+                })
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, path)| path)
+        })
+    }
+}
+
+/// Resolves icon names with the freedesktop icon theme specification.
+///
+/// The configured theme is searched first, then the themes it inherits from and hicolor last.
+/// Icons outside of any theme, for example in `/usr/share/pixmaps`, are used after that,
+/// and the fallback icon if nothing was found.
+/// Names ending in `-symbolic` fall back to the full color icon of the same name.
+/// Results are cached, including icons that were not found.
+#[derive(Debug)]
+pub struct IconLookup {
+    theme: String,
+    base_dirs: Vec<PathBuf>,
+    extensions: Vec<&'static str>,
+    fallback: Option<String>,
+    themes: Mutex<HashMap<String, Option<Arc<IconTheme>>>>,
+    cache: Mutex<HashMap<(String, u32, u32), Option<PathBuf>>>,
+}
+
+impl IconLookup {
+    pub fn new(theme: impl Into<String>, base_dirs: Vec<PathBuf>) -> IconLookup {
+        IconLookup {
+            theme: theme.into(),
+            base_dirs,
+            extensions: vec!["png", "svg"],
+            fallback: None,
+            themes: Mutex::new(HashMap::new()),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// File extensions in order of preference, png and svg by default
+    pub fn extensions(mut self, extensions: &[&'static str]) -> IconLookup {
+        self.extensions = extensions.to_vec();
+        self
+    }
+
+    /// Icon that is returned for names that are not found
+    pub fn fallback(mut self, icon: &str) -> IconLookup {
+        self.fallback = Some(String::from(icon)).filter(|icon| !icon.is_empty());
+        self
+    }
+
+    /// Finds the icon closest to `size` at the given `scale`, icons that are not found
+    /// are reported on stderr the first time they are looked up
+    pub fn find(&self, icon: &str, size: u32, scale: u32) -> Option<PathBuf> {
+        let key = (String::from(icon), size, scale);
+        if let Some(path) = self.cache.lock().unwrap().get(&key) {
+            return path.clone();
+        }
+        let path = self
+            .find_uncached(icon, size, scale)
+            .or_else(|| {
+                let full_color = icon.strip_suffix(SYMBOLIC_SUFFIX)?;
+                self.find_uncached(full_color, size, scale)
+            })
+            .or_else(|| {
+                let fallback = self.fallback.as_deref()?;
+                self.find_uncached(fallback, size, scale)
+            });
+        // misses are cached, so every missing icon is reported once
+        if path.is_none() {
+            eprintln!(
+                "warning: icon {icon} was not found in the {} icon theme and has no fallback",
+                self.theme
+            );
+        }
+        self.cache.lock().unwrap().insert(key, path.clone());
+        path
+    }
+
+    fn find_uncached(&self, icon: &str, size: u32, scale: u32) -> Option<PathBuf> {
+        let mut visited = HashSet::new();
+        self.find_in_theme(&self.theme, icon, size, scale, &mut visited)
+            .or_else(|| self.find_in_theme(HICOLOR, icon, size, scale, &mut visited))
+            .or_else(|| self.find_unthemed(icon))
+    }
+
+    /// `FindIconHelper` of the specification, visited themes are skipped to survive cycles
+    fn find_in_theme(
+        &self,
+        name: &str,
+        icon: &str,
+        size: u32,
+        scale: u32,
+        visited: &mut HashSet<String>,
+    ) -> Option<PathBuf> {
+        if !visited.insert(String::from(name)) {
+            return None;
+        }
+        let theme = self.theme(name)?;
+        theme
+            .lookup(icon, size, scale, &self.extensions)
+            .or_else(|| {
+                theme
+                    .inherits
+                    .iter()
+                    .find_map(|parent| self.find_in_theme(parent, icon, size, scale, visited))
+            })
+    }
+
+    fn find_unthemed(&self, icon: &str) -> Option<PathBuf> {
+        self.base_dirs.iter().find_map(|base| {
+            self.extensions.iter().find_map(|extension| {
+                let path = base.join(format!("{icon}.{extension}"));
+                path.is_file().then_some(path)
+            })
+        })
+    }
+
+    fn theme(&self, name: &str) -> Option<Arc<IconTheme>> {
+        self.themes
+            .lock()
+            .unwrap()
+            .entry(String::from(name))
+            .or_insert_with(|| IconTheme::load(name, &self.base_dirs).map(Arc::new))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+fn write(path: &std::path::Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn test_icon_lookup_follows_inheritance() {
+    let base = std::env::temp_dir().join(format!("oxiced-icons-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&base);
+    write(
+        &base.join("custom/index.theme"),
+        "[Icon Theme]\nName=Custom\nInherits=parent\nDirectories=16x16/actions\n\n\
+         [16x16/actions]\nSize=16\nType=Fixed\n",
+    );
+    write(&base.join("custom/16x16/actions/go-up.svg"), "<svg/>");
+    write(
+        &base.join("parent/index.theme"),
+        "[Icon Theme]\nName=Parent\nDirectories=24x24/actions,scalable/actions\n\n\
+         [24x24/actions]\nSize=24\nType=Fixed\n\n\
+         [scalable/actions]\nSize=48\nMinSize=8\nMaxSize=512\nType=Scalable\n",
+    );
+    write(&base.join("parent/24x24/actions/go-up.svg"), "<svg/>");
+    write(&base.join("parent/scalable/actions/go-down.svg"), "<svg/>");
+    write(
+        &base.join("hicolor/index.theme"),
+        "[Icon Theme]\nName=Hicolor\nDirectories=apps\n\n[apps]\nSize=32\n",
+    );
+    write(&base.join("hicolor/apps/oxiced.svg"), "<svg/>");
+
+    let lookup = IconLookup::new("custom", vec![base.clone()]).fallback("oxiced");
+    // the configured theme wins even with the wrong size
+    assert_eq!(
+        lookup.find("go-up", 24, 1),
+        Some(base.join("custom/16x16/actions/go-up.svg"))
+    );
+    assert_eq!(
+        lookup.find("go-down-symbolic", 64, 1),
+        Some(base.join("parent/scalable/actions/go-down.svg"))
+    );
+    assert_eq!(
+        lookup.find("missing", 24, 1),
+        Some(base.join("hicolor/apps/oxiced.svg"))
+    );
+    let _ = std::fs::remove_dir_all(&base);
+}
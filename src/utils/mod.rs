@@ -1,2 +1,6 @@
+pub mod animation;
 pub mod color;
+pub mod color_vision;
 pub mod file;
+pub mod icon_theme;
+pub mod symbolic;
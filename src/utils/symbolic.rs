@@ -0,0 +1,106 @@
+use iced::Color;
+
+use crate::utils::color::to_hex;
+
+/// Style classes of GNOME symbolic icons that carry their own color
+const SYMBOLIC_CLASSES: [&str; 3] = ["success", "warning", "error"];
+const CURRENT_COLOR: &str = "currentColor";
+
+/// How an svg picks up theme colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SvgColoring {
+    /// Single color icon, tinted as a whole
+    Monochrome,
+    /// Symbolic icon with colored style classes, recolored per class
+    Classes,
+    /// Full color icon, drawn as is
+    FullColor,
+}
+
+/// Colors substituted into symbolic icons, mirrors the stylesheet GTK uses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymbolicColors {
+    pub foreground: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+}
+
+/// Decides how an icon is colored.
+/// `symbolic_name` is set for files named `*-symbolic`, which are single color
+/// even without `currentColor`.
+pub fn coloring(svg: &str, symbolic_name: bool) -> SvgColoring {
+    if classes(svg).any(|class| SYMBOLIC_CLASSES.contains(&class)) {
+        SvgColoring::Classes
+    } else if symbolic_name || svg.contains(CURRENT_COLOR) {
+        SvgColoring::Monochrome
+    } else {
+        SvgColoring::FullColor
+    }
+}
+
+/// Every class used in the svg, attributes are not parsed beyond `class="..."`
+fn classes(svg: &str) -> impl Iterator<Item = &str> {
+    svg.split("class=").skip(1).flat_map(|rest| {
+        let mut chars = rest.chars();
+        let value = match chars.next() {
+            Some(quote @ ('"' | '\'')) => chars.as_str().split(quote).next().unwrap_or_default(),
+            _ => "",
+        };
+        value.split_whitespace()
+    })
+}
+
+/// Substitutes the colors for `currentColor` and the symbolic classes.
+///
+/// Like GTK, shapes are filled with the foreground unless their class says otherwise.
+pub fn recolor(svg: &str, colors: &SymbolicColors) -> String {
+    let foreground = to_hex(&colors.foreground);
+    let svg = svg.replace(CURRENT_COLOR, &format!("#{foreground}"));
+    let stylesheet = format!(
+        "<style type=\"text/css\">\
+         rect,circle,ellipse,path {{ fill: #{foreground} !important; }}\
+         .success {{ fill: #{} !important; }}\
+         .warning {{ fill: #{} !important; }}\
+         .error {{ fill: #{} !important; }}\
+         </style>",
+        to_hex(&colors.success),
+        to_hex(&colors.warning),
+        to_hex(&colors.error),
+    );
+    // the stylesheet goes right behind the opening svg tag
+    let Some(end) = svg
+        .find("<svg")
+        .and_then(|start| svg[start..].find('>').map(|end| start + end + 1))
+    else {
+        return svg;
+    };
+    let mut recolored = String::with_capacity(svg.len() + stylesheet.len());
+    recolored.push_str(&svg[..end]);
+    recolored.push_str(&stylesheet);
+    recolored.push_str(&svg[end..]);
+    recolored
+}
+
+#[test]
+fn test_symbolic_classes_are_recolored() {
+    let colors = SymbolicColors {
+        foreground: Color::WHITE,
+        success: Color::from_rgb8(0, 255, 0),
+        warning: Color::from_rgb8(255, 255, 0),
+        error: Color::from_rgb8(255, 0, 0),
+    };
+    let full_color = r##"<svg viewBox="0 0 16 16"><path fill="#3584e4"/></svg>"##;
+    let single = r#"<svg viewBox="0 0 16 16"><path stroke="currentColor"/></svg>"#;
+    let classes = r#"<svg viewBox="0 0 16 16"><path/><path class="error"/></svg>"#;
+
+    assert_eq!(coloring(full_color, false), SvgColoring::FullColor);
+    assert_eq!(coloring(full_color, true), SvgColoring::Monochrome);
+    assert_eq!(coloring(single, false), SvgColoring::Monochrome);
+    assert_eq!(coloring(classes, false), SvgColoring::Classes);
+
+    assert!(recolor(single, &colors).contains(r##"stroke="#ffffff""##));
+    let recolored = recolor(classes, &colors);
+    assert!(recolored.starts_with(r#"<svg viewBox="0 0 16 16"><style"#));
+    assert!(recolored.contains(".error { fill: #ff0000 !important; }"));
+}
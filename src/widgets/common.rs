@@ -1,11 +1,193 @@
+use iced::Color;
 use iced::theme::palette::{Background, Danger, Pair, Primary, Secondary, Success};
 
+use crate::{
+    theme::theme_impl::ComputedOxiTheme,
+    utils::color::{darken_color, readable_on},
+};
+
+/// Semantic color of a widget, shared by every Oxi widget
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    #[default]
+    Primary,
+    Secondary,
+    Success,
+    Danger,
+    Warning,
+    Info,
+    /// Selector background, for widgets that should not stand out
+    Neutral,
+    /// Sidebar and card background, quieter than neutral
+    Surface,
+    Rose,
+    Lavender,
+    Blue,
+    Mauve,
+    Flamingo,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 13] = [
+        Variant::Primary,
+        Variant::Secondary,
+        Variant::Success,
+        Variant::Danger,
+        Variant::Warning,
+        Variant::Info,
+        Variant::Neutral,
+        Variant::Surface,
+        Variant::Rose,
+        Variant::Lavender,
+        Variant::Blue,
+        Variant::Mauve,
+        Variant::Flamingo,
+    ];
+
+    /// Key of the variant in theme.toml, for example in `[button.<variant>]`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Primary => "primary",
+            Variant::Secondary => "secondary",
+            Variant::Success => "success",
+            Variant::Danger => "danger",
+            Variant::Warning => "warning",
+            Variant::Info => "info",
+            Variant::Neutral => "neutral",
+            Variant::Surface => "surface",
+            Variant::Rose => "rose",
+            Variant::Lavender => "lavender",
+            Variant::Blue => "blue",
+            Variant::Mauve => "mauve",
+            Variant::Flamingo => "flamingo",
+        }
+    }
+
+    /// Former `ButtonVariant::PrimaryBg`
+    #[deprecated(note = "use `Variant::Surface` instead")]
+    #[allow(non_upper_case_globals)]
+    pub const PrimaryBg: Variant = Variant::Surface;
+
+    /// Former `ButtonVariant::SecondaryBg`
+    #[deprecated(note = "use `Variant::Neutral` instead")]
+    #[allow(non_upper_case_globals)]
+    pub const SecondaryBg: Variant = Variant::Neutral;
+}
+
+/// Colors of a [`Variant`] for every interaction state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VariantColors {
+    pub base: Color,
+    pub hover: Color,
+    pub active: Color,
+    /// Text and icons drawn on top of the variant
+    pub contrast: Color,
+}
+
+impl ComputedOxiTheme {
+    /// Colors of a selected control like a checked radio or a toggled toggler.
+    /// [`Variant::Neutral`] and [`Variant::Surface`] are the unselected track of these
+    /// controls, so they are selected with the text color instead.
+    pub fn selected(&self, variant: Variant) -> VariantColors {
+        let colors = self.variant(variant);
+        match variant {
+            Variant::Neutral | Variant::Surface => VariantColors {
+                base: self.text,
+                hover: darken_color(&self.text, self.tint_amount),
+                active: darken_color(&self.text, self.shade_amount),
+                contrast: colors.base,
+            },
+            _ => colors,
+        }
+    }
+
+    pub fn variant(&self, variant: Variant) -> VariantColors {
+        let colors = |base, hover, active, contrast| VariantColors {
+            base,
+            hover,
+            active,
+            contrast,
+        };
+        // the named accents have no precomputed states, they darken like the other accents
+        let accent = |base: Color| {
+            colors(
+                base,
+                darken_color(&base, self.tint_amount),
+                darken_color(&base, self.shade_amount),
+                readable_on(&base),
+            )
+        };
+        match variant {
+            Variant::Primary => colors(
+                self.primary,
+                self.primary_hover,
+                self.primary_active,
+                self.primary_contrast,
+            ),
+            Variant::Secondary => colors(
+                self.secondary,
+                self.secondary_hover,
+                self.secondary_active,
+                self.secondary_contrast,
+            ),
+            Variant::Success => colors(
+                self.good,
+                self.good_hover,
+                self.good_active,
+                self.good_contrast,
+            ),
+            Variant::Danger => colors(self.bad, self.bad_hover, self.bad_active, self.bad_contrast),
+            Variant::Warning => colors(
+                self.warning,
+                self.warning_hover,
+                self.warning_active,
+                self.warning_contrast,
+            ),
+            Variant::Info => colors(
+                self.info,
+                self.info_hover,
+                self.info_active,
+                self.info_contrast,
+            ),
+            Variant::Neutral => colors(
+                self.secondary_bg,
+                self.secondary_bg_hover,
+                self.secondary_bg_active,
+                self.text,
+            ),
+            Variant::Surface => colors(
+                self.primary_bg,
+                self.primary_bg_hover,
+                self.primary_bg_active,
+                self.text,
+            ),
+            Variant::Rose => accent(self.rose),
+            Variant::Lavender => accent(self.lavender),
+            Variant::Blue => accent(self.blue),
+            Variant::Mauve => accent(self.mauve),
+            Variant::Flamingo => accent(self.flamingo),
+        }
+    }
+}
+
+/// Restyles a widget with a [`Variant`], implemented for every Oxi widget.
+///
+/// ```ignore
+/// slider(0..=100, value, Message::Volume).variant(Variant::Danger)
+/// ```
+pub trait WithVariant {
+    #[must_use]
+    fn variant(self, variant: Variant) -> Self;
+}
+
+#[deprecated(note = "use `Variant` and `ComputedOxiTheme::variant` instead")]
 pub trait StylingCategory {
     fn base(&self) -> Pair;
     fn weak(&self) -> Pair;
     fn strong(&self) -> Pair;
 }
 
+#[allow(deprecated)]
 impl StylingCategory for Background {
     fn base(&self) -> Pair {
         self.base
@@ -20,6 +202,7 @@ impl StylingCategory for Background {
     }
 }
 
+#[allow(deprecated)]
 impl StylingCategory for Primary {
     fn base(&self) -> Pair {
         self.base
@@ -34,6 +217,7 @@ impl StylingCategory for Primary {
     }
 }
 
+#[allow(deprecated)]
 impl StylingCategory for Secondary {
     fn base(&self) -> Pair {
         self.base
@@ -48,6 +232,7 @@ impl StylingCategory for Secondary {
     }
 }
 
+#[allow(deprecated)]
 impl StylingCategory for Success {
     fn base(&self) -> Pair {
         self.base
@@ -62,6 +247,7 @@ impl StylingCategory for Success {
     }
 }
 
+#[allow(deprecated)]
 impl StylingCategory for Danger {
     fn base(&self) -> Pair {
         self.base
@@ -75,3 +261,86 @@ impl StylingCategory for Danger {
         self.strong
     }
 }
+
+/// Weak is the hover and strong the active state of the variant
+#[allow(deprecated)]
+impl StylingCategory for VariantColors {
+    fn base(&self) -> Pair {
+        Pair {
+            color: self.base,
+            text: self.contrast,
+        }
+    }
+
+    fn weak(&self) -> Pair {
+        Pair {
+            color: self.hover,
+            text: self.contrast,
+        }
+    }
+
+    fn strong(&self) -> Pair {
+        Pair {
+            color: self.active,
+            text: self.contrast,
+        }
+    }
+}
+
+#[test]
+fn test_derived_variants_are_readable() {
+    use crate::{theme::theme_impl::default_theme, utils::color::contrast_ratio};
+
+    let theme = ComputedOxiTheme::from(default_theme());
+    // the semantic colors bring their own contrast color from the theme
+    let derived = [
+        Variant::Neutral,
+        Variant::Surface,
+        Variant::Rose,
+        Variant::Lavender,
+        Variant::Blue,
+        Variant::Mauve,
+        Variant::Flamingo,
+    ];
+    for variant in derived {
+        let colors = theme.variant(variant);
+        assert!(
+            contrast_ratio(&colors.contrast, &colors.base) >= 4.5,
+            "{} is not readable",
+            variant.name()
+        );
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_variants_map_onto_variant() {
+    use crate::widgets::{oxi_button::ButtonVariant, oxi_svg::SvgStyleVariant};
+
+    assert_eq!(ButtonVariant::PrimaryBg, Variant::Surface);
+    assert_eq!(ButtonVariant::SecondaryBg, Variant::Neutral);
+    assert_eq!(ButtonVariant::Danger, Variant::Danger);
+    assert_eq!(SvgStyleVariant::Secondary, Variant::Secondary);
+    assert!(matches!(Variant::Neutral, ButtonVariant::SecondaryBg));
+}
+
+#[test]
+fn test_selected_background_variants_stand_out() {
+    use crate::{theme::theme_impl::default_theme, utils::color::contrast_ratio};
+
+    let theme = ComputedOxiTheme::from(default_theme());
+    for variant in [Variant::Neutral, Variant::Surface] {
+        let selected = theme.selected(variant).base;
+        for track in [theme.primary_bg, theme.secondary_bg] {
+            assert!(
+                contrast_ratio(&selected, &track) >= 3.0,
+                "{}",
+                variant.name()
+            );
+        }
+    }
+    assert_eq!(
+        theme.selected(Variant::Primary),
+        theme.variant(Variant::Primary)
+    );
+}
@@ -1,14 +1,19 @@
 pub mod common;
+pub mod oxi_bar;
 pub mod oxi_button;
 pub mod oxi_card;
 pub mod oxi_checkbox;
+pub mod oxi_collapse;
 pub mod oxi_icon;
 pub mod oxi_layer;
+pub mod oxi_menu_button;
 pub mod oxi_picklist;
 pub mod oxi_progress;
 pub mod oxi_radio;
 pub mod oxi_rule;
+pub mod oxi_segmented;
 pub mod oxi_slider;
+pub mod oxi_spinner;
 pub mod oxi_svg;
 pub mod oxi_text_input;
 pub mod oxi_toggler;
@@ -0,0 +1,487 @@
+use iced::advanced::Clipboard;
+use iced::advanced::Layout;
+use iced::advanced::Shell;
+use iced::advanced::Widget;
+use iced::advanced::layout;
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::Operation;
+use iced::advanced::widget::tree::{self, Tree};
+use iced::mouse;
+use iced::{Element, Event, Length, Padding, Point, Rectangle, Size, Vector};
+
+use crate::theme::theme_impl::OXITHEME;
+
+/// Direction the sections of a [`Bar`] are laid out in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+/// Part of a [`Bar`] an item sits in, start is left or top
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Start,
+    Center,
+    End,
+}
+
+/// Items with a lower priority are hidden first when a [`Bar`] is too short
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// Status bar layout with start, center and end sections.
+///
+/// The center section stays centered on the bar no matter how wide the sides are.
+/// Items that don't fit are hidden by [`Priority`], the latest added first.
+#[allow(missing_debug_implementations)]
+pub struct Bar<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    children: Vec<Element<'a, Message, Theme, Renderer>>,
+    items: Vec<(Section, Priority)>,
+    orientation: Orientation,
+    thickness: f32,
+    padding: Padding,
+    spacing: f32,
+}
+
+/// The state of the [`Bar`]
+#[derive(Debug, Default)]
+struct State {
+    hidden: Vec<bool>,
+}
+
+impl<'a, Message, Theme, Renderer> Bar<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    pub fn new() -> Self {
+        let palette = &OXITHEME;
+        Bar {
+            children: Vec::new(),
+            items: Vec::new(),
+            orientation: Orientation::Horizontal,
+            thickness: palette.bar_height,
+            padding: Padding::from([palette.padding_xs, palette.padding_md]),
+            spacing: palette.padding_md,
+        }
+    }
+
+    /// Lays out the sections from top to bottom, padding is turned along
+    pub fn vertical(mut self) -> Self {
+        if self.orientation == Orientation::Horizontal {
+            self.orientation = Orientation::Vertical;
+            self.padding = Padding {
+                top: self.padding.left,
+                right: self.padding.top,
+                bottom: self.padding.right,
+                left: self.padding.bottom,
+            };
+        }
+        self
+    }
+
+    /// Height of a horizontal bar or width of a vertical one
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn push(
+        mut self,
+        section: Section,
+        priority: Priority,
+        item: impl Into<Element<'a, Message, Theme, Renderer>>,
+    ) -> Self {
+        self.children.push(item.into());
+        self.items.push((section, priority));
+        self
+    }
+
+    pub fn start(self, item: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        self.push(Section::Start, Priority::Normal, item)
+    }
+
+    pub fn center(self, item: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        self.push(Section::Center, Priority::Normal, item)
+    }
+
+    pub fn end(self, item: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        self.push(Section::End, Priority::Normal, item)
+    }
+
+    /// Splits a size into its length along the bar and across it
+    fn main_cross(&self, size: Size) -> (f32, f32) {
+        match self.orientation {
+            Orientation::Horizontal => (size.width, size.height),
+            Orientation::Vertical => (size.height, size.width),
+        }
+    }
+
+    fn point(&self, main: f32, cross: f32) -> Point {
+        match self.orientation {
+            Orientation::Horizontal => Point::new(main, cross),
+            Orientation::Vertical => Point::new(cross, main),
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> Default for Bar<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Length of the visible items of a section including the spacing between them
+fn section_length(
+    section: Section,
+    lengths: &[f32],
+    items: &[(Section, Priority)],
+    hidden: &[bool],
+    spacing: f32,
+) -> f32 {
+    let visible: Vec<f32> = lengths
+        .iter()
+        .zip(items)
+        .zip(hidden)
+        .filter(|((_, (item_section, _)), hidden)| *item_section == section && !**hidden)
+        .map(|((length, _), _)| *length)
+        .collect();
+    visible.iter().sum::<f32>() + spacing * visible.len().saturating_sub(1) as f32
+}
+
+/// Length the visible items need with the center section centered
+fn required_length(
+    lengths: &[f32],
+    items: &[(Section, Priority)],
+    hidden: &[bool],
+    spacing: f32,
+) -> f32 {
+    let length = |section| section_length(section, lengths, items, hidden, spacing);
+    let (start, center, end) = (
+        length(Section::Start),
+        length(Section::Center),
+        length(Section::End),
+    );
+    if center > 0.0 {
+        let side = start.max(end);
+        center + 2.0 * if side > 0.0 { side + spacing } else { 0.0 }
+    } else {
+        let between = if start > 0.0 && end > 0.0 {
+            spacing
+        } else {
+            0.0
+        };
+        start + end + between
+    }
+}
+
+/// Hides items until the rest fits, lowest priority and latest added first
+fn hidden_items(
+    lengths: &[f32],
+    items: &[(Section, Priority)],
+    available: f32,
+    spacing: f32,
+) -> Vec<bool> {
+    let mut hidden = vec![false; items.len()];
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|index| (items[*index].1, std::cmp::Reverse(*index)));
+    for index in order {
+        if required_length(lengths, items, &hidden, spacing) <= available {
+            break;
+        }
+        hidden[index] = true;
+    }
+    hidden
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Bar<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.children.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.children);
+    }
+
+    fn size(&self) -> Size<Length> {
+        match self.orientation {
+            Orientation::Horizontal => Size::new(Length::Fill, Length::Fixed(self.thickness)),
+            Orientation::Vertical => Size::new(Length::Fixed(self.thickness), Length::Fill),
+        }
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let size = self.size();
+        let size = limits.width(size.width).height(size.height).resolve(
+            size.width,
+            size.height,
+            Size::ZERO,
+        );
+        let inner = Size::new(
+            (size.width - self.padding.x()).max(0.0),
+            (size.height - self.padding.y()).max(0.0),
+        );
+        let (inner_main, inner_cross) = self.main_cross(inner);
+        let (padding_main, padding_cross) = match self.orientation {
+            Orientation::Horizontal => (self.padding.left, self.padding.top),
+            Orientation::Vertical => (self.padding.top, self.padding.left),
+        };
+        let child_limits = layout::Limits::new(Size::ZERO, inner);
+
+        let mut nodes: Vec<layout::Node> = self
+            .children
+            .iter_mut()
+            .zip(&mut tree.children)
+            .map(|(child, tree)| child.as_widget_mut().layout(tree, renderer, &child_limits))
+            .collect();
+        let lengths: Vec<f32> = nodes
+            .iter()
+            .map(|node| self.main_cross(node.size()).0)
+            .collect();
+        let hidden = hidden_items(&lengths, &self.items, inner_main, self.spacing);
+
+        let length =
+            |section| section_length(section, &lengths, &self.items, &hidden, self.spacing);
+        let mut offsets = [
+            (Section::Start, 0.0),
+            (
+                Section::Center,
+                (inner_main - length(Section::Center)) / 2.0,
+            ),
+            (Section::End, inner_main - length(Section::End)),
+        ];
+
+        for (index, node) in nodes.iter_mut().enumerate() {
+            if hidden[index] {
+                *node = layout::Node::new(Size::ZERO);
+                continue;
+            }
+            let offset = offsets
+                .iter_mut()
+                .find(|(section, _)| *section == self.items[index].0)
+                .map(|(_, offset)| offset)
+                .unwrap();
+            let (main, cross) = self.main_cross(node.size());
+            let position = self.point(
+                padding_main + *offset,
+                padding_cross + (inner_cross - cross) / 2.0,
+            );
+            node.move_to_mut(position);
+            *offset += main + self.spacing;
+        }
+
+        tree.state.downcast_mut::<State>().hidden = hidden;
+        layout::Node::with_children(size, nodes)
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let hidden = &tree.state.downcast_ref::<State>().hidden;
+        operation.container(None, layout.bounds());
+        operation.traverse(&mut |operation| {
+            self.children
+                .iter_mut()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+                .zip(hidden)
+                .filter(|(_, hidden)| !**hidden)
+                .for_each(|(((child, state), layout), _)| {
+                    child
+                        .as_widget_mut()
+                        .operate(state, layout, renderer, operation);
+                });
+        });
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let hidden = &tree.state.downcast_ref::<State>().hidden;
+        for (((child, tree), layout), _) in self
+            .children
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+            .zip(hidden)
+            .filter(|(_, hidden)| !**hidden)
+        {
+            child.as_widget_mut().update(
+                tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+            );
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let hidden = &tree.state.downcast_ref::<State>().hidden;
+        self.children
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .zip(hidden)
+            .filter(|(_, hidden)| !**hidden)
+            .map(|(((child, tree), layout), _)| {
+                child
+                    .as_widget()
+                    .mouse_interaction(tree, layout, cursor, viewport, renderer)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let hidden = &tree.state.downcast_ref::<State>().hidden;
+        for (((child, tree), layout), _) in self
+            .children
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .zip(hidden)
+            .filter(|(_, hidden)| !**hidden)
+        {
+            child
+                .as_widget()
+                .draw(tree, renderer, theme, style, layout, cursor, viewport);
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let hidden = &tree.state.downcast_ref::<State>().hidden;
+        let overlays: Vec<_> = self
+            .children
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+            .zip(hidden)
+            .filter(|(_, hidden)| !**hidden)
+            .filter_map(|(((child, tree), layout), _)| {
+                child
+                    .as_widget_mut()
+                    .overlay(tree, layout, renderer, viewport, translation)
+            })
+            .collect();
+        (!overlays.is_empty()).then(|| overlay::Group::with_children(overlays).overlay())
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Bar<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(bar: Bar<'a, Message, Theme, Renderer>) -> Element<'a, Message, Theme, Renderer> {
+        Element::new(bar)
+    }
+}
+
+pub fn bar<'a, Message, Theme, Renderer>() -> Bar<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    Bar::new()
+}
+
+#[test]
+fn test_bar_hides_low_priority_items_first() {
+    let items = [
+        (Section::Start, Priority::High),
+        (Section::Start, Priority::Low),
+        (Section::Center, Priority::High),
+        (Section::End, Priority::Normal),
+        (Section::End, Priority::Low),
+    ];
+    let lengths = [40.0, 40.0, 100.0, 30.0, 30.0];
+    let spacing = 10.0;
+
+    // the center is flanked by the longer side on both ends: 100 + 2 * (90 + 10)
+    assert_eq!(
+        required_length(&lengths, &items, &[false; 5], spacing),
+        300.0
+    );
+    assert_eq!(hidden_items(&lengths, &items, 300.0, spacing), [false; 5]);
+
+    // hiding the low priority end item alone doesn't help, the start side is still the longest
+    assert_eq!(
+        hidden_items(&lengths, &items, 299.0, spacing),
+        [false, true, false, false, true]
+    );
+    // among equal priorities the latest added goes first
+    assert_eq!(
+        hidden_items(&lengths, &items, 150.0, spacing),
+        [false, true, true, true, true]
+    );
+}
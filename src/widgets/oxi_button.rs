@@ -1,34 +1,80 @@
 use iced::{
-    Border, Color, Element, Length, Shadow, Theme, Vector,
-    border::Radius,
-    widget::button::{Status, Style},
+    Alignment, Border, Color, Element, Length, Shadow, Theme,
+    advanced::text::IntoFragment,
+    widget::{
+        button::{Status, Style},
+        row, svg,
+    },
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::{
+    theme::theme_impl::{ComponentSize, Elevation, OXITHEME},
+    utils::color::{CONTRAST_AA, CONTRAST_AAA, darken_color, ensure_contrast_on_all, mix_oklab},
+    widgets::{
+        common::{Variant, WithVariant},
+        oxi_icon::{Icon, icon_widget},
+        oxi_spinner::Spinner,
+        oxi_svg::ThemedSvg,
+    },
+};
+
+/// How much of the variant is mixed into the background of tonal buttons
+const TONAL_MIX: f32 = 0.75;
+/// Each interaction state moves the tonal background this much closer to the variant
+const TONAL_STATE_STEP: f32 = 0.1;
+/// Opacity of the variant behind outlined and ghost buttons
+const HOVER_ALPHA: f32 = 0.12;
+const PRESSED_ALPHA: f32 = 0.2;
+
+/// `ButtonVariant::PrimaryBg` and `SecondaryBg` map to [`Variant::Surface`] and [`Variant::Neutral`]
+#[deprecated(note = "use `Variant` instead")]
+pub type ButtonVariant = Variant;
+
+/// Outline of a button that only contains an icon
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IconButtonShape {
+    #[default]
+    Square,
+    Circle,
+}
 
-pub enum ButtonVariant {
-    Primary,
-    Secondary,
-    Success,
-    Danger,
-    PrimaryBg,
-    SecondaryBg,
+/// Side of the label the icon is placed on
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IconPosition {
+    #[default]
+    Leading,
+    Trailing,
 }
 
-fn styled(background: Color, text: Color, shadow: Color) -> Style {
+/// Visual weight of a button, independent of its [`Variant`].
+///
+/// The `[button.<variant>]` overrides of theme.toml only restyle [`ButtonKind::Filled`],
+/// the other kinds derive every color from the variant.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonKind {
+    /// Solid variant background, for the main action
+    #[default]
+    Filled,
+    /// Background tinted with the variant
+    Tonal,
+    /// Transparent with a border in the variant
+    Outlined,
+    /// Transparent until hovered
+    Ghost,
+    /// Only text, for inline actions
+    Link,
+}
+
+fn styled(background: Color, text: Color) -> Style {
     Style {
         background: Some(iced::Background::Color(background)),
         text_color: text,
         border: Border {
             color: iced::Color::TRANSPARENT,
             width: 0.0,
-            radius: Radius::from(OXITHEME.border_radius as u16),
-        },
-        shadow: Shadow {
-            color: shadow,
-            offset: Vector { x: 0.2, y: 0.2 },
-            blur_radius: 2.0,
+            radius: OXITHEME.radius(ComponentSize::Input),
         },
+        shadow: OXITHEME.shadow(Elevation::Low),
         snap: false,
     }
 }
@@ -43,8 +89,16 @@ fn disabled(style: Style) -> Style {
     }
 }
 
-fn states(status: Status, base: Style, pressed: Color, hovered: Color) -> Style {
-    match status {
+/// `override_key` is the `[button.<variant>]` table that applies, only filled buttons use them
+fn states(
+    status: Status,
+    override_key: Option<&str>,
+    base: Style,
+    pressed: Color,
+    hovered: Color,
+) -> Style {
+    let palette = &OXITHEME;
+    let style = match status {
         Status::Active => base,
         Status::Pressed => Style {
             background: Some(iced::Background::Color(pressed)),
@@ -55,86 +109,316 @@ fn states(status: Status, base: Style, pressed: Color, hovered: Color) -> Style
             ..base
         },
         Status::Disabled => disabled(base),
+    };
+    let style = if palette.high_contrast {
+        high_contrast(status, style)
+    } else {
+        style
+    };
+    // like every widget, overrides are applied last so they also restyle high contrast mode
+    match override_key.and_then(|key| palette.button.get(key)) {
+        Some(style_override) => Style {
+            background: style_override
+                .background
+                .map(|background| {
+                    iced::Background::Color(match status {
+                        Status::Active => background,
+                        Status::Hovered => darken_color(&background, palette.tint_amount),
+                        Status::Pressed => darken_color(&background, palette.shade_amount),
+                        Status::Disabled if palette.high_contrast => background,
+                        Status::Disabled => background.scale_alpha(0.5),
+                    })
+                })
+                .or(style.background),
+            text_color: match style_override.text {
+                Some(text) if status == Status::Disabled && !palette.high_contrast => {
+                    text.scale_alpha(0.5)
+                }
+                Some(text) => text,
+                None => style.text_color,
+            },
+            border: style_override.border(style.border),
+            ..style
+        },
+        None => style,
     }
 }
 
-pub fn primary_button(_: &Theme, status: Status) -> Style {
+/// Adds a strong border that thickens on interaction and replaces the translucent disabled state
+fn high_contrast(status: Status, style: Style) -> Style {
     let palette = &OXITHEME;
-    let base = styled(palette.primary, palette.primary_contrast, palette.primary);
-    states(status, base, palette.primary_active, palette.primary_hover)
+    let width = match status {
+        Status::Hovered | Status::Pressed => palette.focus_border_width,
+        Status::Active | Status::Disabled => palette.high_contrast_border_width,
+    };
+    let border = Border {
+        color: palette.border_color_strong,
+        width,
+        ..style.border
+    };
+    match status {
+        Status::Disabled => Style {
+            background: Some(iced::Background::Color(palette.primary_bg)),
+            text_color: palette.text_muted,
+            border: Border {
+                color: palette.text_muted,
+                ..border
+            },
+            ..style
+        },
+        _ => Style { border, ..style },
+    }
 }
 
-pub fn primary_bg_button(_: &Theme, status: Status) -> Style {
-    let palette = &OXITHEME;
-    let color = palette.primary_bg;
-    let base = styled(color, palette.text, color);
+/// Style of a button in any [`Variant`], theme.toml can override it in `[button.<variant>]`
+pub fn button_style(variant: Variant, _: &Theme, status: Status) -> Style {
+    let colors = OXITHEME.variant(variant);
+    let base = styled(colors.base, colors.contrast);
     states(
         status,
+        Some(variant.name()),
         base,
-        palette.primary_bg_active,
-        palette.primary_bg_hover,
+        colors.active,
+        colors.hover,
     )
 }
 
-pub fn secondary_bg_button(_: &Theme, status: Status) -> Style {
+/// Style of a button in any [`Variant`] and [`ButtonKind`]
+pub fn button_kind_style(
+    variant: Variant,
+    kind: ButtonKind,
+    theme: &Theme,
+    status: Status,
+) -> Style {
     let palette = &OXITHEME;
-    let color = palette.secondary_bg;
-    let base = styled(color, palette.text, color);
-    states(
-        status,
-        base,
-        palette.secondary_bg_active,
-        palette.secondary_bg_hover,
-    )
+    let colors = palette.variant(variant);
+    // surface variants are backgrounds, without a fill their text color is used
+    let foreground = match variant {
+        Variant::Neutral | Variant::Surface => colors.contrast,
+        _ => colors.base,
+    };
+    let min_contrast = if palette.high_contrast {
+        CONTRAST_AAA
+    } else {
+        CONTRAST_AA
+    };
+    // unfilled kinds draw the variant as text on whatever background they sit on
+    let foreground = if palette.high_contrast && kind != ButtonKind::Filled {
+        ensure_contrast_on_all(&foreground, &palette.text_backgrounds(), min_contrast)
+    } else {
+        foreground
+    };
+    let transparent = |border_width: f32| Style {
+        border: Border {
+            color: foreground,
+            width: border_width,
+            ..styled(Color::TRANSPARENT, foreground).border
+        },
+        shadow: Shadow::default(),
+        ..styled(Color::TRANSPARENT, foreground)
+    };
+    match kind {
+        ButtonKind::Filled => button_style(variant, theme, status),
+        ButtonKind::Tonal => {
+            // tinted towards the window background the button sits on
+            let tone = |step: f32| mix_oklab(&colors.base, &palette.mantle, TONAL_MIX - step);
+            let (background, hovered, pressed) = (
+                tone(0.0),
+                tone(TONAL_STATE_STEP),
+                tone(2.0 * TONAL_STATE_STEP),
+            );
+            let text =
+                ensure_contrast_on_all(&foreground, &[background, hovered, pressed], min_contrast);
+            states(status, None, styled(background, text), pressed, hovered)
+        }
+        ButtonKind::Outlined => states(
+            status,
+            None,
+            transparent(palette.border_width(ComponentSize::Control)),
+            foreground.scale_alpha(PRESSED_ALPHA),
+            foreground.scale_alpha(HOVER_ALPHA),
+        ),
+        ButtonKind::Ghost => states(
+            status,
+            None,
+            transparent(0.0),
+            foreground.scale_alpha(PRESSED_ALPHA),
+            foreground.scale_alpha(HOVER_ALPHA),
+        ),
+        // text colored states would be invisible on the surface variants, they fade in like ghosts
+        ButtonKind::Link if matches!(variant, Variant::Neutral | Variant::Surface) => states(
+            status,
+            None,
+            transparent(0.0),
+            foreground.scale_alpha(PRESSED_ALPHA),
+            foreground.scale_alpha(HOVER_ALPHA),
+        ),
+        ButtonKind::Link => {
+            let text_color = match status {
+                Status::Hovered => colors.hover,
+                Status::Pressed => colors.active,
+                Status::Active | Status::Disabled => foreground,
+            };
+            let style = Style {
+                text_color,
+                ..transparent(0.0)
+            };
+            match status {
+                _ if palette.high_contrast => high_contrast(status, style),
+                Status::Disabled => disabled(style),
+                _ => style,
+            }
+        }
+    }
 }
 
-pub fn secondary_button(_: &Theme, status: Status) -> Style {
-    let palette = &OXITHEME;
-    let base = styled(
-        palette.secondary,
-        palette.secondary_contrast,
-        palette.secondary,
-    );
-    states(
-        status,
-        base,
-        palette.secondary_active,
-        palette.secondary_hover,
-    )
+pub fn primary_button(theme: &Theme, status: Status) -> Style {
+    button_style(Variant::Primary, theme, status)
 }
 
-pub fn success_button(_: &Theme, status: Status) -> Style {
-    let palette = &OXITHEME;
-    let base = styled(palette.good, palette.good_contrast, palette.good);
-    states(status, base, palette.good_active, palette.good_hover)
+pub fn primary_bg_button(theme: &Theme, status: Status) -> Style {
+    button_style(Variant::Surface, theme, status)
 }
 
-pub fn danger_button(_: &Theme, status: Status) -> Style {
-    let palette = &OXITHEME;
-    let base = styled(palette.bad, palette.bad_contrast, palette.bad);
-    states(status, base, palette.bad_active, palette.bad_hover)
+pub fn secondary_bg_button(theme: &Theme, status: Status) -> Style {
+    button_style(Variant::Neutral, theme, status)
+}
+
+pub fn secondary_button(theme: &Theme, status: Status) -> Style {
+    button_style(Variant::Secondary, theme, status)
+}
+
+pub fn success_button(theme: &Theme, status: Status) -> Style {
+    button_style(Variant::Success, theme, status)
+}
+
+pub fn danger_button(theme: &Theme, status: Status) -> Style {
+    button_style(Variant::Danger, theme, status)
 }
 
 pub fn button<'a, M>(
     content: impl Into<Element<'a, M>>,
-    variant: ButtonVariant,
+    variant: Variant,
 ) -> iced::widget::Button<'a, M> {
-    let style = match variant {
-        ButtonVariant::Primary => primary_button,
-        ButtonVariant::Secondary => secondary_button,
-        ButtonVariant::Success => success_button,
-        ButtonVariant::Danger => danger_button,
-        ButtonVariant::PrimaryBg => primary_bg_button,
-        ButtonVariant::SecondaryBg => secondary_bg_button,
-    };
     iced::widget::button(content)
         .padding(OXITHEME.padding_md)
-        .style(style)
+        .variant(variant)
+}
+
+/// Button in a [`ButtonKind`] other than filled, links have no padding so they fit into text
+pub fn kind_button<'a, M>(
+    content: impl Into<Element<'a, M>>,
+    variant: Variant,
+    kind: ButtonKind,
+) -> iced::widget::Button<'a, M> {
+    let padding = match kind {
+        ButtonKind::Link => 0.0,
+        _ => OXITHEME.padding_md,
+    };
+    iced::widget::button(content)
+        .padding(padding)
+        .style(move |theme, status| button_kind_style(variant, kind, theme, status))
+}
+
+/// Icon in the text color of the button, sized like the label
+pub(crate) fn button_icon<'a, I: Icon>(
+    icon: I,
+    variant: Variant,
+    kind: ButtonKind,
+) -> ThemedSvg<'a> {
+    icon_widget(icon)
+        .width(OXITHEME.font_lg)
+        .height(OXITHEME.font_lg)
+        .style(move |theme, _| svg::Style {
+            color: Some(button_kind_style(variant, kind, theme, Status::Active).text_color),
+        })
+}
+
+/// Button that only shows an icon, describe the action with a tooltip
+pub fn icon_button<'a, M, I: Icon>(
+    icon: I,
+    variant: Variant,
+    kind: ButtonKind,
+    shape: IconButtonShape,
+) -> iced::widget::Button<'a, M> {
+    let padding = OXITHEME.padding_sm;
+    let size = OXITHEME.font_lg + 2.0 * padding;
+    iced::widget::button(button_icon(icon, variant, kind))
+        .padding(padding)
+        .width(size)
+        .height(size)
+        .style(move |theme, status| {
+            let style = button_kind_style(variant, kind, theme, status);
+            match shape {
+                IconButtonShape::Square => style,
+                IconButtonShape::Circle => Style {
+                    border: Border {
+                        radius: OXITHEME.radius(ComponentSize::Pill),
+                        ..style.border
+                    },
+                    ..style
+                },
+            }
+        })
+}
+
+/// Button with a label and an icon on one side of it
+pub fn icon_text_button<'a, M: 'a, I: Icon>(
+    icon: I,
+    label: impl IntoFragment<'a>,
+    position: IconPosition,
+    variant: Variant,
+    kind: ButtonKind,
+) -> iced::widget::Button<'a, M> {
+    let icon = button_icon(icon, variant, kind);
+    let label = iced::widget::text(label);
+    let content = match position {
+        IconPosition::Leading => row![icon, label],
+        IconPosition::Trailing => row![label, icon],
+    };
+    kind_button(
+        content
+            .spacing(OXITHEME.padding_sm)
+            .align_y(Alignment::Center),
+        variant,
+        kind,
+    )
+}
+
+/// Shows a [`Spinner`] instead of the content while loading.
+/// The button keeps its size and ignores presses without looking disabled.
+pub fn loading_button<'a, M: Clone + 'a>(
+    content: impl Into<Element<'a, M>>,
+    variant: Variant,
+    kind: ButtonKind,
+    is_loading: bool,
+    on_press: M,
+) -> iced::widget::Button<'a, M> {
+    let content: Element<'a, M> = if is_loading {
+        Spinner::over(content).into()
+    } else {
+        content.into()
+    };
+    kind_button(content, variant, kind)
+        .on_press_maybe((!is_loading).then_some(on_press))
+        .style(move |theme, status| {
+            let status = match status {
+                Status::Disabled if is_loading => Status::Active,
+                status => status,
+            };
+            button_kind_style(variant, kind, theme, status)
+        })
 }
 
 pub fn row_button<'a, M>(
     content: impl Into<Element<'a, M>>,
-    variant: ButtonVariant,
+    variant: Variant,
 ) -> iced::widget::Button<'a, M> {
     button(content, variant).width(Length::Fill)
 }
+
+impl<M> WithVariant for iced::widget::Button<'_, M> {
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme, status| button_style(variant, theme, status))
+    }
+}
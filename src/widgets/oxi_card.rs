@@ -2,18 +2,21 @@ use iced::{
     Alignment, Element, Length, Theme,
     alignment::{Horizontal, Vertical},
     border,
-    widget::{Row, column, container::Style, row, text},
+    widget::{Column, Row, column, container, container::Style, row, svg::Handle, text},
 };
 
 use crate::{
-    theme::theme_impl::OXITHEME,
+    theme::theme_impl::{ComponentSize, Elevation, OXITHEME},
     widgets::{
-        oxi_button::{self, ButtonVariant},
-        oxi_icon::icon_widget,
+        common::Variant,
+        oxi_button::{self, ButtonKind},
+        oxi_collapse::Collapse,
+        oxi_icon::{Icon, chevron, icon_widget},
+        oxi_svg::svg_from_handle,
     },
 };
 
-pub enum CardHeader<'a, T: Clone, I: ToString> {
+pub enum CardHeader<'a, T: Clone, I: Icon> {
     Title(String),
     TitleWithIcon {
         title: String,
@@ -26,93 +29,253 @@ pub enum CardHeader<'a, T: Clone, I: ToString> {
     Custom(Element<'a, T>),
 }
 
-impl<'a, T: Clone + 'a, I: ToString> CardHeader<'a, T, I> {
-    fn mk_title(
-        title: String,
-        icon_opt: Option<I>,
-        element: Option<Element<'a, T>>,
-    ) -> Element<'a, T> {
-        let palette = &OXITHEME;
-        let mut elems: Vec<Element<'a, T>> = vec![
-            text(title).size(palette.font_xl).into(),
-            column!(element.unwrap_or(row!().into()))
-                .width(Length::Fill)
-                .align_x(Alignment::End)
-                .into(),
-        ];
-        if let Some(icon) = icon_opt {
-            elems.insert(0, icon_widget::<I>(icon).into());
+/// Click handler of a [`Card`]
+enum OnClick<'a, T> {
+    Message(T),
+    Closure(Box<dyn Fn() -> T + 'a>),
+}
+
+/// Container with an optional header and footer, see [`Card::new`].
+///
+/// A card is clickable as a whole, or expandable by clicking its header.
+pub struct Card<'a, T: Clone + 'a> {
+    title: Option<String>,
+    subtitle: Option<String>,
+    icon: Option<Handle>,
+    header_element: Option<Element<'a, T>>,
+    custom_header: Option<Element<'a, T>>,
+    body: Element<'a, T>,
+    actions: Vec<Element<'a, T>>,
+    on_click: Option<OnClick<'a, T>>,
+    expanded: Option<bool>,
+    on_toggle: Option<Box<dyn Fn(bool) -> T + 'a>>,
+}
+
+impl<'a, T: Clone + 'a> Card<'a, T> {
+    pub fn new(body: impl Into<Element<'a, T>>) -> Self {
+        Self {
+            title: None,
+            subtitle: None,
+            icon: None,
+            header_element: None,
+            custom_header: None,
+            body: body.into(),
+            actions: Vec::new(),
+            on_click: None,
+            expanded: None,
+            on_toggle: None,
         }
-        Row::from_vec(elems).padding(palette.padding_lg).into()
     }
 
-    fn mk_header(self) -> Element<'a, T> {
-        match self {
-            CardHeader::Title(title) => Self::mk_title(title, None, None),
-            CardHeader::TitleWithIcon { title, icon } => Self::mk_title(title, Some(icon), None),
+    pub fn mk_title_card(title: String, body: Element<'a, T>) -> Element<'a, T> {
+        Self::new(body).title(title).into()
+    }
+
+    pub fn header<I: Icon>(self, header: CardHeader<'a, T, I>) -> Self {
+        match header {
+            CardHeader::Title(title) => self.title(title),
+            CardHeader::TitleWithIcon { title, icon } => self.title(title).icon(icon),
             CardHeader::TitleWithElement { title, element } => {
-                Self::mk_title(title, None, Some(element))
+                self.title(title).header_element(element)
             }
-            CardHeader::Custom(element) => element,
+            CardHeader::Custom(element) => Self {
+                custom_header: Some(element),
+                ..self
+            },
         }
     }
-}
 
-pub struct Card<'a, T: Clone + 'a, I: ToString> {
-    header: Option<CardHeader<'a, T, I>>,
-    body: Element<'a, T>,
-    on_click: Option<fn() -> T>,
-}
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
 
-impl<'a, T: Clone + 'a> Card<'a, T, String> {
-    pub fn mk_title_card(title: String, body: Element<'a, T>) -> Element<'a, T> {
-        Self {
-            header: Some(CardHeader::Title(title)),
-            body,
-            on_click: None,
-        }
-        .view()
+    /// Smaller line below the title
+    pub fn subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn icon(mut self, icon: impl Icon) -> Self {
+        self.icon = Some(icon.handle(OXITHEME.icon_size));
+        self
+    }
+
+    /// Element at the end of the header, like a switch or a badge
+    pub fn header_element(mut self, element: impl Into<Element<'a, T>>) -> Self {
+        self.header_element = Some(element.into());
+        self
+    }
+
+    /// Adds a button to the footer, actions are aligned to the end of the card
+    pub fn action(mut self, action: impl Into<Element<'a, T>>) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+
+    pub fn on_click(mut self, message: T) -> Self {
+        self.on_click = Some(OnClick::Message(message));
+        self
+    }
+
+    pub fn on_click_with(mut self, on_click: impl Fn() -> T + 'a) -> Self {
+        self.on_click = Some(OnClick::Closure(Box::new(on_click)));
+        self
+    }
+
+    /// Shows the body and footer only while expanded, clicking the header
+    /// produces `on_toggle` with the new state
+    pub fn expandable(mut self, expanded: bool, on_toggle: impl Fn(bool) -> T + 'a) -> Self {
+        self.expanded = Some(expanded);
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
     }
-}
 
-impl<'a, T: Clone + 'a, I: ToString + 'a> Card<'a, T, I> {
     fn style(_: &Theme) -> Style {
         let palette = &OXITHEME;
 
         Style {
             background: Some(palette.mantle.into()),
-            border: border::rounded(palette.border_radius as u16),
+            border: if palette.high_contrast {
+                border::color(palette.border_color_strong)
+                    .width(palette.border_width(ComponentSize::Container))
+                    .rounded(palette.radius(ComponentSize::Container))
+            } else {
+                border::rounded(palette.radius(ComponentSize::Container))
+            },
+            shadow: palette.shadow(Elevation::Low),
             ..Style::default()
         }
     }
 
-    fn clickable_card(
-        element: impl Into<Element<'a, T>>,
-        on_click: fn() -> T,
-    ) -> impl Into<Element<'a, T>> {
-        oxi_button::button(element, ButtonVariant::PrimaryBg).on_press_with(on_click)
+    fn mk_title(
+        title: Option<String>,
+        subtitle: Option<String>,
+        icon: Option<Handle>,
+        element: Option<Element<'a, T>>,
+        expanded: Option<bool>,
+    ) -> Element<'a, T> {
+        let palette = &OXITHEME;
+        let titles = column![]
+            .push(title.map(|title| text(title).size(palette.font_xl)))
+            .push(subtitle.map(|subtitle| {
+                text(subtitle)
+                    .size(palette.font_md)
+                    .color(palette.text_muted)
+            }));
+        let chevron = expanded.map(|expanded| {
+            // points up while the card is expanded
+            icon_widget(chevron(expanded))
+                .width(palette.icon_size as f32)
+                .height(palette.icon_size as f32)
+        });
+        row![]
+            .push(icon.map(|icon| {
+                svg_from_handle(Variant::Primary, icon)
+                    .width(palette.icon_size as f32)
+                    .height(palette.icon_size as f32)
+            }))
+            .push(titles)
+            .push(
+                column!(element.unwrap_or(row!().into()))
+                    .width(Length::Fill)
+                    .align_x(Alignment::End),
+            )
+            .push(chevron)
+            .spacing(palette.padding_md)
+            .align_y(Alignment::Center)
+            .padding(palette.padding_lg)
+            .into()
+    }
+
+    /// Message of a click on the header of an expandable card
+    fn toggle(&self) -> Option<T> {
+        let expanded = self.expanded?;
+        self.on_toggle
+            .as_ref()
+            .map(|on_toggle| on_toggle(!expanded))
     }
 
     fn view(self) -> Element<'a, T> {
-        let card = iced::widget::container(
-            column!(
-                self.header
-                    .map(|value| value.mk_header())
-                    .unwrap_or(row!().into()),
-                self.body,
-            )
-            .spacing(OXITHEME.padding_md)
-            .width(Length::Fill)
-            .height(Length::Fill),
+        let palette = &OXITHEME;
+        let expanded = self.expanded;
+        let toggle = self.toggle();
+        let has_title = self.title.is_some()
+            || self.subtitle.is_some()
+            || self.icon.is_some()
+            || self.header_element.is_some();
+        let header = match self.custom_header {
+            Some(header) => Some(header),
+            None if has_title || expanded.is_some() => Some(Self::mk_title(
+                self.title,
+                self.subtitle,
+                self.icon,
+                self.header_element,
+                expanded,
+            )),
+            None => None,
+        };
+        let header = match (header, toggle) {
+            (Some(header), Some(toggle)) => Some(
+                oxi_button::kind_button(header, Variant::Surface, ButtonKind::Ghost)
+                    .padding(0)
+                    .width(Length::Fill)
+                    .on_press(toggle)
+                    .into(),
+            ),
+            (header, _) => header,
+        };
+        let footer = (!self.actions.is_empty()).then(|| {
+            container(Row::from_vec(self.actions).spacing(palette.padding_md))
+                .width(Length::Fill)
+                .align_x(Horizontal::Right)
+        });
+        let content = column![self.body]
+            .push(footer)
+            .spacing(palette.padding_md)
+            .width(Length::Fill);
+        let content: Element<'a, T> = match expanded {
+            Some(expanded) => Collapse::new(content, expanded).into(),
+            None => content.height(Length::Fill).into(),
+        };
+
+        let card = container(
+            Column::new()
+                .push(header)
+                .push(content)
+                .spacing(palette.padding_md)
+                .width(Length::Fill),
         )
-        .padding(OXITHEME.padding_lg)
+        .padding(palette.padding_lg)
         .style(Self::style)
         .align_x(Horizontal::Center)
         .align_y(Vertical::Center);
-        if let Some(click) = self.on_click {
-            Self::clickable_card(card, click).into()
-        } else {
-            card.into()
+        match self.on_click {
+            Some(on_click) => {
+                let button = oxi_button::button(card, Variant::Surface);
+                match on_click {
+                    OnClick::Message(message) => button.on_press(message),
+                    OnClick::Closure(on_click) => button.on_press_with(on_click),
+                }
+                .into()
+            }
+            None => card.into(),
         }
     }
 }
+
+impl<'a, T: Clone + 'a> From<Card<'a, T>> for Element<'a, T> {
+    fn from(card: Card<'a, T>) -> Self {
+        card.view()
+    }
+}
+
+#[test]
+fn test_card_header_toggles_expansion() {
+    let collapsed = Card::new(text("body")).expandable(false, |expanded| expanded);
+    assert_eq!(collapsed.toggle(), Some(true));
+    let expanded = Card::new(text("body")).expandable(true, |expanded| expanded);
+    assert_eq!(expanded.toggle(), Some(false));
+    let plain: Card<'_, bool> = Card::new(text("body")).on_click(true);
+    assert_eq!(plain.toggle(), None);
+}
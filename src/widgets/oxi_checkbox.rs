@@ -1,6 +1,5 @@
 use iced::{
     Border, Theme,
-    border::Radius,
     widget::{
         Checkbox,
         checkbox::{Status, Style},
@@ -8,35 +7,49 @@ use iced::{
     },
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::{
+    theme::theme_impl::{ComponentSize, OXITHEME},
+    widgets::common::{Variant, WithVariant},
+};
+
+pub fn checkbox_style(theme: &Theme, status: Status) -> Style {
+    checkbox_variant_style(Variant::Primary, theme, status)
+}
 
-pub fn checkbox_style(_: &Theme, status: Status) -> Style {
+/// Checked boxes are filled with the variant, unchecked ones look the same for every variant
+pub fn checkbox_variant_style(variant: Variant, _: &Theme, status: Status) -> Style {
     let palette = &OXITHEME;
+    let colors = palette.variant(variant);
     let mut style = Style {
         background: iced::Background::Color(palette.tertiary_bg),
         text_color: Some(palette.text),
         border: Border {
             color: palette.border_color_weak,
-            width: 1.0,
-            radius: Radius::from(4),
+            width: palette.border_width(ComponentSize::Control),
+            radius: palette.radius(ComponentSize::Control),
         },
         icon_color: palette.text,
     };
     let mut checked_style = Style {
-        background: iced::Background::Color(palette.primary),
+        background: iced::Background::Color(colors.base),
         border: Border {
-            color: palette.primary_contrast,
+            color: colors.contrast,
             ..style.border
         },
-        icon_color: palette.primary_contrast,
+        icon_color: colors.contrast,
         ..style
     };
-    match status {
+    let (is_checked, state) = match status {
+        Status::Active { is_checked } => (is_checked, None),
+        Status::Hovered { is_checked } => (is_checked, Some("hovered")),
+        Status::Disabled { is_checked } => (is_checked, Some("disabled")),
+    };
+    let style = match status {
         Status::Active {
             is_checked: checked,
         } => match checked {
             true => {
-                checked_style.background = iced::Background::Color(palette.primary_active);
+                checked_style.background = iced::Background::Color(colors.active);
                 checked_style
             }
             false => style,
@@ -45,7 +58,7 @@ pub fn checkbox_style(_: &Theme, status: Status) -> Style {
             is_checked: checked,
         } => match checked {
             true => {
-                checked_style.background = iced::Background::Color(palette.primary_hover);
+                checked_style.background = iced::Background::Color(colors.hover);
                 checked_style
             }
             false => {
@@ -66,7 +79,35 @@ pub fn checkbox_style(_: &Theme, status: Status) -> Style {
                 style
             }
         },
+    };
+    let style = if palette.high_contrast {
+        high_contrast(status, style)
+    } else {
+        style
+    };
+    let checked_state = if is_checked { "checked" } else { "unchecked" };
+    [Some(checked_state), state]
+        .into_iter()
+        .flatten()
+        .filter_map(|key| palette.checkbox.get(key))
+        .fold(style, |style, style_override| Style {
+            background: style_override.background(style.background),
+            icon_color: style_override.text(style.icon_color),
+            border: style_override.border(style.border),
+            ..style
+        })
+}
+
+/// Uses strong borders that thicken on hover, so state is not only expressed by color
+fn high_contrast(status: Status, mut style: Style) -> Style {
+    let palette = &OXITHEME;
+    style.border.color = palette.border_color_strong;
+    match status {
+        Status::Hovered { .. } => style.border.width = palette.focus_border_width,
+        Status::Disabled { .. } => style.icon_color = palette.text_muted,
+        Status::Active { .. } => {}
     }
+    style
 }
 
 pub fn checkbox<'a, M>(
@@ -82,3 +123,9 @@ pub fn checkbox<'a, M>(
         .text_line_height(LineHeight::Relative(2.0))
         .on_toggle(user_on_toggle)
 }
+
+impl<M> WithVariant for Checkbox<'_, M> {
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme, status| checkbox_variant_style(variant, theme, status))
+    }
+}
@@ -0,0 +1,274 @@
+use iced::advanced::Clipboard;
+use iced::advanced::Layout;
+use iced::advanced::Shell;
+use iced::advanced::Widget;
+use iced::advanced::layout;
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::Operation;
+use iced::advanced::widget::tree::{self, Tree};
+use iced::mouse;
+use iced::time::Instant;
+use iced::window;
+use iced::{Element, Event, Length, Rectangle, Size, Vector};
+use lilt::Animated;
+
+use crate::theme::theme_impl::{Motion, OXITHEME};
+
+/// Content that grows to its full height when expanded and shrinks away when collapsed.
+///
+/// Collapsed content receives no events.
+#[allow(missing_debug_implementations)]
+pub struct Collapse<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    expanded: bool,
+}
+
+/// The state of the [`Collapse`]
+#[derive(Debug)]
+struct State {
+    expanded: Animated<bool, Instant>,
+    now: Instant,
+}
+
+impl State {
+    /// Share of the content height that is shown
+    fn progress(&self) -> f32 {
+        self.expanded.animate_bool(0.0, 1.0, self.now)
+    }
+
+    /// Starts growing or shrinking, returns whether the target changed
+    fn expand(&mut self, expanded: bool, at: Instant) -> bool {
+        let changed = self.expanded.value != expanded;
+        self.expanded.transition(expanded, at);
+        changed
+    }
+
+    /// Moves to the next frame, returns whether the height changed since the last frame.
+    /// The frame that finishes the animation is no longer in progress but still changes it.
+    fn advance(&mut self, now: Instant) -> bool {
+        let changed = self.expanded.in_progress(self.now) || self.expanded.in_progress(now);
+        self.now = now;
+        changed
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Collapse<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>, expanded: bool) -> Self {
+        Collapse {
+            content: content.into(),
+            expanded,
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Collapse<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State {
+            expanded: OXITHEME.animated(self.expanded, Motion::Transition),
+            now: Instant::now(),
+        })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.content.as_widget().size().width, Length::Shrink)
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let progress = tree.state.downcast_ref::<State>().progress();
+        let node = self
+            .content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits);
+        let size = node.size();
+        layout::Node::with_children(Size::new(size.width, size.height * progress), vec![node])
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        if tree.state.downcast_ref::<State>().expanded.value {
+            self.content.as_widget_mut().operate(
+                &mut tree.children[0],
+                layout.children().next().unwrap(),
+                renderer,
+                operation,
+            );
+        }
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        if let Event::Window(window::Event::RedrawRequested(now)) = event {
+            if state.advance(*now) {
+                shell.invalidate_layout();
+            }
+            if state.expanded.in_progress(*now) {
+                shell.request_redraw();
+            }
+        }
+        if state.expand(self.expanded, Instant::now()) {
+            shell.invalidate_layout();
+            shell.request_redraw();
+        }
+        if state.expanded.value {
+            self.content.as_widget_mut().update(
+                &mut tree.children[0],
+                event,
+                layout.children().next().unwrap(),
+                cursor,
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            );
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if !tree.state.downcast_ref::<State>().expanded.value {
+            return mouse::Interaction::default();
+        }
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout.children().next().unwrap(),
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let Some(clipped) = bounds.intersection(viewport) else {
+            return;
+        };
+        if bounds.height <= 0.0 {
+            return;
+        }
+        renderer.with_layer(clipped, |renderer| {
+            self.content.as_widget().draw(
+                &tree.children[0],
+                renderer,
+                theme,
+                style,
+                layout.children().next().unwrap(),
+                cursor,
+                &clipped,
+            );
+        });
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        if !tree.state.downcast_ref::<State>().expanded.value {
+            return None;
+        }
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout.children().next().unwrap(),
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Collapse<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(
+        collapse: Collapse<'a, Message, Theme, Renderer>,
+    ) -> Element<'a, Message, Theme, Renderer> {
+        Element::new(collapse)
+    }
+}
+
+#[test]
+fn test_collapse_grows_and_settles() {
+    use std::time::Duration;
+
+    let start = Instant::now();
+    let mut state = State {
+        expanded: Animated::new(false).duration(200.0),
+        now: start,
+    };
+    assert!(!state.expand(false, start));
+    assert!(state.expand(true, start));
+    assert_eq!(state.progress(), 0.0);
+
+    assert!(state.advance(start + Duration::from_millis(100)));
+    assert!(state.progress() > 0.0 && state.progress() < 1.0);
+    // the last frame still lays the content out at its full height
+    assert!(state.advance(start + Duration::from_millis(300)));
+    assert_eq!(state.progress(), 1.0);
+    assert!(!state.advance(start + Duration::from_millis(400)));
+
+    assert!(state.expand(false, start + Duration::from_millis(400)));
+    state.advance(start + Duration::from_secs(1));
+    assert_eq!(state.progress(), 0.0);
+}
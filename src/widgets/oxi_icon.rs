@@ -1,17 +1,193 @@
+use iced::widget::svg::Handle;
 use once_cell::sync::Lazy;
 
-use crate::widgets::oxi_svg::{SvgStyleVariant, svg_from_path};
+use crate::{
+    theme::theme_impl::OXITHEME,
+    utils::icon_theme::ICON_LOOKUP,
+    widgets::{
+        common::Variant,
+        oxi_svg::{ThemedSvg, svg_from_handle, svg_from_path},
+    },
+};
 
+#[deprecated(note = "icons are resolved in the icon theme, see `IconLookup`")]
 pub static ICONPATH: Lazy<&'static str> = Lazy::new(|| "./assets/{}.svg");
 
-fn path<I: ToString>(icon: I) -> String {
-    ICONPATH.replace("{}", &icon.to_string())
+/// Anything that can be drawn by [`icon_widget`].
+///
+/// Names are resolved in the icon theme, with the `embedded-icons` feature
+/// [`OxiIcon`] is drawn from the icons bundled with the library.
+pub trait Icon {
+    /// Svg handle of the icon, `size` picks the closest icon theme directory
+    fn handle(&self, size: u32) -> Handle;
 }
 
-pub fn icon_widget<'a, I: ToString>(icon: I) -> iced::widget::Svg<'a> {
-    svg_from_path(SvgStyleVariant::Primary, path(icon))
+impl<T: ToString> Icon for T {
+    fn handle(&self, size: u32) -> Handle {
+        Handle::from_path(path(self, size))
+    }
 }
 
-pub fn icon_widget_from_plain_path<'a>(plain_path: impl Into<String>) -> iced::widget::Svg<'a> {
-    svg_from_path(SvgStyleVariant::Primary, plain_path.into())
+/// Symbolic icons bundled with the library, available without an icon theme.
+#[cfg(feature = "embedded-icons")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OxiIcon {
+    Close,
+    Back,
+    Forward,
+    Search,
+    Settings,
+    ChevronUp,
+    ChevronDown,
+    ChevronLeft,
+    ChevronRight,
+    Check,
+    Warning,
+    Info,
+    Error,
+    Add,
+    Remove,
+    Menu,
+    Refresh,
+}
+
+#[cfg(feature = "embedded-icons")]
+macro_rules! embedded {
+    ($name:literal) => {
+        include_bytes!(concat!("../../assets/icons/", $name, ".svg"))
+    };
+}
+
+#[cfg(feature = "embedded-icons")]
+impl OxiIcon {
+    pub const ALL: [OxiIcon; 17] = [
+        OxiIcon::Close,
+        OxiIcon::Back,
+        OxiIcon::Forward,
+        OxiIcon::Search,
+        OxiIcon::Settings,
+        OxiIcon::ChevronUp,
+        OxiIcon::ChevronDown,
+        OxiIcon::ChevronLeft,
+        OxiIcon::ChevronRight,
+        OxiIcon::Check,
+        OxiIcon::Warning,
+        OxiIcon::Info,
+        OxiIcon::Error,
+        OxiIcon::Add,
+        OxiIcon::Remove,
+        OxiIcon::Menu,
+        OxiIcon::Refresh,
+    ];
+
+    /// Freedesktop name of the icon, the bundled file carries the same name
+    pub fn name(self) -> &'static str {
+        match self {
+            OxiIcon::Close => "window-close-symbolic",
+            OxiIcon::Back => "go-previous-symbolic",
+            OxiIcon::Forward => "go-next-symbolic",
+            OxiIcon::Search => "system-search-symbolic",
+            OxiIcon::Settings => "emblem-system-symbolic",
+            OxiIcon::ChevronUp => "pan-up-symbolic",
+            OxiIcon::ChevronDown => "pan-down-symbolic",
+            OxiIcon::ChevronLeft => "pan-start-symbolic",
+            OxiIcon::ChevronRight => "pan-end-symbolic",
+            OxiIcon::Check => "object-select-symbolic",
+            OxiIcon::Warning => "dialog-warning-symbolic",
+            OxiIcon::Info => "dialog-information-symbolic",
+            OxiIcon::Error => "dialog-error-symbolic",
+            OxiIcon::Add => "list-add-symbolic",
+            OxiIcon::Remove => "list-remove-symbolic",
+            OxiIcon::Menu => "open-menu-symbolic",
+            OxiIcon::Refresh => "view-refresh-symbolic",
+        }
+    }
+
+    /// Contents of the bundled svg
+    pub fn bytes(self) -> &'static [u8] {
+        match self {
+            OxiIcon::Close => embedded!("window-close-symbolic"),
+            OxiIcon::Back => embedded!("go-previous-symbolic"),
+            OxiIcon::Forward => embedded!("go-next-symbolic"),
+            OxiIcon::Search => embedded!("system-search-symbolic"),
+            OxiIcon::Settings => embedded!("emblem-system-symbolic"),
+            OxiIcon::ChevronUp => embedded!("pan-up-symbolic"),
+            OxiIcon::ChevronDown => embedded!("pan-down-symbolic"),
+            OxiIcon::ChevronLeft => embedded!("pan-start-symbolic"),
+            OxiIcon::ChevronRight => embedded!("pan-end-symbolic"),
+            OxiIcon::Check => embedded!("object-select-symbolic"),
+            OxiIcon::Warning => embedded!("dialog-warning-symbolic"),
+            OxiIcon::Info => embedded!("dialog-information-symbolic"),
+            OxiIcon::Error => embedded!("dialog-error-symbolic"),
+            OxiIcon::Add => embedded!("list-add-symbolic"),
+            OxiIcon::Remove => embedded!("list-remove-symbolic"),
+            OxiIcon::Menu => embedded!("open-menu-symbolic"),
+            OxiIcon::Refresh => embedded!("view-refresh-symbolic"),
+        }
+    }
+}
+
+#[cfg(feature = "embedded-icons")]
+impl Icon for OxiIcon {
+    fn handle(&self, _size: u32) -> Handle {
+        Handle::from_memory(self.bytes())
+    }
+}
+
+/// Resolves an icon name in the configured icon theme, see
+/// [`IconLookup`](crate::utils::icon_theme::IconLookup).
+/// Icons that are not found are drawn empty, the lookup reports them once.
+fn path<I: ToString + ?Sized>(icon: &I, size: u32) -> String {
+    ICON_LOOKUP
+        .find(&icon.to_string(), size, 1)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Chevron pointing up or down, bundled with the `embedded-icons` feature
+#[cfg(feature = "embedded-icons")]
+pub(crate) fn chevron(up: bool) -> impl Icon {
+    if up {
+        OxiIcon::ChevronUp
+    } else {
+        OxiIcon::ChevronDown
+    }
+}
+
+/// Chevron pointing up or down, bundled with the `embedded-icons` feature
+#[cfg(not(feature = "embedded-icons"))]
+pub(crate) fn chevron(up: bool) -> impl Icon {
+    if up {
+        "pan-up-symbolic"
+    } else {
+        "pan-down-symbolic"
+    }
+}
+
+/// Icon from the icon theme at the size configured in theme.toml
+pub fn icon_widget<'a, I: Icon>(icon: I) -> ThemedSvg<'a> {
+    sized_icon_widget(icon, OXITHEME.icon_size)
+}
+
+/// Icon from the icon theme, picked from the directory closest to `size`
+pub fn sized_icon_widget<'a, I: Icon>(icon: I, size: u32) -> ThemedSvg<'a> {
+    svg_from_handle(Variant::Primary, icon.handle(size))
+}
+
+pub fn icon_widget_from_plain_path<'a>(plain_path: impl Into<String>) -> ThemedSvg<'a> {
+    svg_from_path(Variant::Primary, plain_path.into())
+}
+
+#[cfg(all(test, feature = "embedded-icons"))]
+#[test]
+fn test_embedded_icons_are_symbolic_svgs() {
+    for icon in OxiIcon::ALL {
+        let svg = std::str::from_utf8(icon.bytes()).unwrap();
+        assert!(svg.starts_with("<svg"), "{} is not an svg", icon.name());
+        assert!(
+            svg.contains("currentColor"),
+            "{} is not symbolic",
+            icon.name()
+        );
+    }
 }
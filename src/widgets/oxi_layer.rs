@@ -1,17 +1,22 @@
 use iced::{
-    Alignment, Element, Length, Theme, theme,
+    Alignment, Element, Length, Shadow, Theme, theme,
     widget::container::{self, Container, Style},
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::theme::theme_impl::{ComponentSize, Elevation, OXITHEME};
 
-fn box_style(theme: &Theme) -> Style {
+fn box_style(theme: &Theme, opacity: f32) -> Style {
     let palette = &OXITHEME;
+    let shadow = palette.shadow(Elevation::High);
     Style {
-        background: Some(iced::Background::Color(palette.mantle)),
-        border: iced::border::color(palette.primary)
-            .width(3)
-            .rounded(palette.border_radius as u16),
+        background: Some(iced::Background::Color(palette.mantle.scale_alpha(opacity))),
+        border: iced::border::color(palette.primary.scale_alpha(opacity))
+            .width(palette.border_width(ComponentSize::Window))
+            .rounded(palette.radius(ComponentSize::Window)),
+        shadow: Shadow {
+            color: shadow.color.scale_alpha(opacity),
+            ..shadow
+        },
         ..container::rounded_box(theme)
     }
 }
@@ -19,10 +24,20 @@ fn box_style(theme: &Theme) -> Style {
 pub fn rounded_layer<'a, T: 'a>(
     content: impl Into<Element<'a, T>>,
     max_size: (u32, u32),
+) -> Element<'a, T> {
+    faded_layer(content, max_size, 1.0)
+}
+
+/// [`rounded_layer`] with a translucent box, for fading surfaces in and out.
+/// The content has to fade itself.
+pub fn faded_layer<'a, T: 'a>(
+    content: impl Into<Element<'a, T>>,
+    max_size: (u32, u32),
+    opacity: f32,
 ) -> Element<'a, T> {
     let palette = &OXITHEME;
     Container::new(content)
-        .style(box_style)
+        .style(move |theme| box_style(theme, opacity))
         .align_x(Alignment::Center)
         .padding(palette.padding_xl + palette.padding_xl)
         .max_width(max_size.0)
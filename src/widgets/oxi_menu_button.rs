@@ -0,0 +1,481 @@
+use iced::advanced::Clipboard;
+use iced::advanced::Layout;
+use iced::advanced::Shell;
+use iced::advanced::Widget;
+use iced::advanced::layout;
+use iced::advanced::overlay;
+use iced::advanced::renderer;
+use iced::advanced::widget::Operation;
+use iced::advanced::widget::operation::Focusable;
+use iced::advanced::widget::tree::{self, Tree};
+use iced::alignment::Horizontal;
+use iced::keyboard;
+use iced::keyboard::key::Named;
+use iced::mouse;
+use iced::overlay::menu::{self, Menu};
+use iced::touch;
+use iced::widget::button::{Status, Style};
+use iced::widget::row;
+use iced::{
+    Background, Border, Color, Element, Event, Length, Padding, Rectangle, Size, Theme, Vector,
+};
+
+use crate::theme::theme_impl::{ComponentSize, OXITHEME};
+use crate::widgets::common::Variant;
+use crate::widgets::oxi_button::{ButtonKind, button_icon, button_kind_style, kind_button};
+use crate::widgets::oxi_icon::chevron;
+use crate::widgets::oxi_picklist::menu_style;
+
+/// Menu width for triggers that are narrower, such as the arrow of a split button
+const MIN_MENU_WIDTH: f32 = 180.0;
+
+/// A styling function for the button of a [`MenuButton`]
+pub type StyleFn<'a> = Box<dyn Fn(&Theme, Status) -> Style + 'a>;
+
+/// Button that opens a list of actions anchored under it.
+///
+/// The menu closes on a click outside of it or on Escape.
+/// The button is focused by a click or by a focus operation, while focused Enter, Space
+/// or the down arrow open the menu with its first action selected.
+/// While it is open, the arrow keys, Home and End move the selection and Enter or Space
+/// activates it.
+#[allow(missing_debug_implementations)]
+pub struct MenuButton<'a, T, Message>
+where
+    T: ToString + Clone,
+{
+    content: Element<'a, Message>,
+    actions: Vec<T>,
+    on_select: Box<dyn Fn(T) -> Message + 'a>,
+    padding: Padding,
+    width: Length,
+    height: Length,
+    menu_width: Option<f32>,
+    menu_alignment: Horizontal,
+    style: StyleFn<'a>,
+    menu_class: menu::StyleFn<'a, Theme>,
+}
+
+/// The state of the [`MenuButton`]
+#[derive(Debug, Default)]
+struct State {
+    menu: menu::State,
+    is_open: bool,
+    is_hovered: bool,
+    is_focused: bool,
+    hovered_action: Option<usize>,
+}
+
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+    }
+}
+
+impl<'a, T, Message> MenuButton<'a, T, Message>
+where
+    T: ToString + Clone,
+{
+    pub fn new(
+        content: impl Into<Element<'a, Message>>,
+        actions: impl Into<Vec<T>>,
+        on_select: impl Fn(T) -> Message + 'a,
+    ) -> Self {
+        MenuButton {
+            content: content.into(),
+            actions: actions.into(),
+            on_select: Box::new(on_select),
+            padding: OXITHEME.padding_md.into(),
+            width: Length::Shrink,
+            height: Length::Shrink,
+            menu_width: None,
+            menu_alignment: Horizontal::Left,
+            style: Box::new(|theme, status| {
+                button_kind_style(Variant::Primary, ButtonKind::Filled, theme, status)
+            }),
+            menu_class: Box::new(menu_style),
+        }
+    }
+
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets the width of the menu, by default it is as wide as the button
+    /// but at least [`MIN_MENU_WIDTH`]
+    pub fn menu_width(mut self, width: f32) -> Self {
+        self.menu_width = Some(width);
+        self
+    }
+
+    /// Aligns the menu with the left or right edge of the button
+    pub fn menu_alignment(mut self, alignment: Horizontal) -> Self {
+        self.menu_alignment = alignment;
+        self
+    }
+
+    /// Sets the style of the button itself, the menu always uses
+    /// [`menu_style`](crate::widgets::oxi_picklist::menu_style)
+    #[must_use]
+    pub fn style(mut self, style: impl Fn(&Theme, Status) -> Style + 'a) -> Self {
+        self.style = Box::new(style);
+        self
+    }
+}
+
+impl<T, Message> Widget<Message, Theme, iced::Renderer> for MenuButton<'_, T, Message>
+where
+    T: ToString + Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::padded(limits, self.width, self.height, self.padding, |limits| {
+            self.content
+                .as_widget_mut()
+                .layout(&mut tree.children[0], renderer, limits)
+        })
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        _renderer: &iced::Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        operation.focusable(None, layout.bounds(), state);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &iced::Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        let is_mouse_over = cursor.is_over(layout.bounds());
+        let count = self.actions.len();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if state.is_open {
+                    // the menu did not capture the click, so it was outside of the menu
+                    close(state, shell);
+                } else if is_mouse_over && count > 0 {
+                    state.is_focused = true;
+                    open(state, None, shell);
+                } else if state.is_focused {
+                    state.is_focused = false;
+                    shell.request_redraw();
+                }
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                ..
+            }) if state.is_focused && !state.is_open => match key {
+                Named::Enter | Named::Space | Named::ArrowDown if count > 0 => {
+                    open(state, Some(0), shell);
+                }
+                Named::Escape | Named::Tab => {
+                    state.is_focused = false;
+                    shell.request_redraw();
+                }
+                _ => {}
+            },
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                ..
+            }) if state.is_open => {
+                match key {
+                    Named::Escape => {
+                        close(state, shell);
+                        return;
+                    }
+                    Named::Enter | Named::Space => {
+                        if let Some(action) = state.hovered_action.and_then(|i| self.actions.get(i))
+                        {
+                            shell.publish((self.on_select)(action.clone()));
+                        }
+                        close(state, shell);
+                        return;
+                    }
+                    Named::ArrowDown => {
+                        state.hovered_action =
+                            Some(state.hovered_action.map_or(0, |i| (i + 1) % count));
+                    }
+                    Named::ArrowUp => {
+                        state.hovered_action = Some(
+                            state
+                                .hovered_action
+                                .map_or(count - 1, |i| (i + count - 1) % count),
+                        );
+                    }
+                    Named::Home => state.hovered_action = Some(0),
+                    Named::End => state.hovered_action = Some(count - 1),
+                    _ => return,
+                }
+                shell.capture_event();
+                shell.request_redraw();
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) if state.is_hovered != is_mouse_over => {
+                state.is_hovered = is_mouse_over;
+                shell.request_redraw();
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &iced::Renderer,
+    ) -> mouse::Interaction {
+        if !cursor.is_over(layout.bounds()) {
+            mouse::Interaction::default()
+        } else if self.actions.is_empty() {
+            mouse::Interaction::NotAllowed
+        } else {
+            mouse::Interaction::Pointer
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let status = if self.actions.is_empty() {
+            Status::Disabled
+        } else if state.is_open {
+            Status::Pressed
+        } else if cursor.is_over(bounds) {
+            Status::Hovered
+        } else {
+            Status::Active
+        };
+        let mut style = (self.style)(theme, status);
+        if state.is_focused && !state.is_open {
+            style.border.width = OXITHEME.focus_border_width;
+            style.border.color = OXITHEME.border_color_strong;
+        }
+
+        if style.background.is_some() || style.border.width > 0.0 || style.shadow.color.a > 0.0 {
+            renderer::Renderer::fill_quad(
+                renderer,
+                renderer::Quad {
+                    bounds,
+                    border: style.border,
+                    shadow: style.shadow,
+                    snap: style.snap,
+                },
+                style
+                    .background
+                    .unwrap_or(Background::Color(Color::TRANSPARENT)),
+            );
+        }
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            &renderer::Style {
+                text_color: style.text_color,
+            },
+            layout.children().next().unwrap(),
+            cursor,
+            &bounds.intersection(viewport).unwrap_or(*viewport),
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        _renderer: &iced::Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, iced::Renderer>> {
+        let state = tree.state.downcast_mut::<State>();
+        if !state.is_open {
+            return None;
+        }
+        let bounds = layout.bounds();
+        let width = self
+            .menu_width
+            .unwrap_or_else(|| bounds.width.max(MIN_MENU_WIDTH));
+        let x = match self.menu_alignment {
+            Horizontal::Right => bounds.x + bounds.width - width,
+            Horizontal::Left | Horizontal::Center => bounds.x,
+        };
+        let on_select = &self.on_select;
+        let menu = Menu::new(
+            &mut state.menu,
+            &self.actions,
+            &mut state.hovered_action,
+            |action| {
+                state.is_open = false;
+                on_select(action)
+            },
+            None,
+            &self.menu_class,
+        )
+        .width(width)
+        .padding(OXITHEME.padding_md)
+        .text_size(OXITHEME.font_md);
+
+        Some(menu.overlay(
+            iced::Point::new(x, bounds.y) + translation,
+            *viewport,
+            bounds.height,
+            Length::Shrink,
+        ))
+    }
+}
+
+impl<'a, T, Message> From<MenuButton<'a, T, Message>> for Element<'a, Message>
+where
+    T: ToString + Clone + 'a,
+    Message: 'a,
+{
+    fn from(menu_button: MenuButton<'a, T, Message>) -> Element<'a, Message> {
+        Element::new(menu_button)
+    }
+}
+
+fn open<Message>(state: &mut State, hovered_action: Option<usize>, shell: &mut Shell<'_, Message>) {
+    state.is_open = true;
+    state.hovered_action = hovered_action;
+    shell.capture_event();
+    shell.request_redraw();
+}
+
+fn close<Message>(state: &mut State, shell: &mut Shell<'_, Message>) {
+    state.is_open = false;
+    state.hovered_action = None;
+    shell.capture_event();
+    shell.request_redraw();
+}
+
+/// Rounds only the outer corners of a button that is joined with another one
+fn joined(style: Style, is_start: bool) -> Style {
+    let radius = style.border.radius;
+    let radius = if is_start {
+        radius.top_right(0.0).bottom_right(0.0)
+    } else {
+        radius.top_left(0.0).bottom_left(0.0)
+    };
+    Style {
+        border: Border {
+            radius,
+            ..style.border
+        },
+        ..style
+    }
+}
+
+/// [`MenuButton`] in the look of [`oxi_button`](crate::widgets::oxi_button)
+pub fn menu_button<'a, T, M>(
+    content: impl Into<Element<'a, M>>,
+    actions: impl Into<Vec<T>>,
+    on_select: impl Fn(T) -> M + 'a,
+    variant: Variant,
+    kind: ButtonKind,
+) -> MenuButton<'a, T, M>
+where
+    T: ToString + Clone,
+{
+    MenuButton::new(content, actions, on_select)
+        .style(move |theme, status| button_kind_style(variant, kind, theme, status))
+}
+
+/// Primary action joined with an arrow that opens the secondary actions
+pub fn split_button<'a, T, M>(
+    content: impl Into<Element<'a, M>>,
+    on_press: M,
+    actions: impl Into<Vec<T>>,
+    on_select: impl Fn(T) -> M + 'a,
+    variant: Variant,
+    kind: ButtonKind,
+) -> Element<'a, M>
+where
+    T: ToString + Clone + 'a,
+    M: Clone + 'a,
+{
+    let action = kind_button(content, variant, kind)
+        .on_press(on_press)
+        .style(move |theme, status| joined(button_kind_style(variant, kind, theme, status), true));
+    let arrow = MenuButton::new(
+        button_icon(chevron(false), variant, kind),
+        actions,
+        on_select,
+    )
+    .padding(OXITHEME.padding_sm)
+    .height(Length::Fill)
+    .menu_alignment(Horizontal::Right)
+    .style(move |theme, status| joined(button_kind_style(variant, kind, theme, status), false));
+    // the gap separates the two halves of filled and tonal buttons
+    row![action, arrow]
+        .spacing(OXITHEME.border_width(ComponentSize::Control))
+        .height(Length::Shrink)
+        .into()
+}
@@ -2,12 +2,11 @@ use std::borrow::Borrow;
 
 use iced::{
     Border, Theme,
-    border::Radius,
     overlay::menu,
     widget::{self, PickList},
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::theme::theme_impl::{ComponentSize, OXITHEME};
 
 pub fn picklist_style(_: &Theme, status: widget::pick_list::Status) -> widget::pick_list::Style {
     let palette = &OXITHEME;
@@ -16,37 +15,67 @@ pub fn picklist_style(_: &Theme, status: widget::pick_list::Status) -> widget::p
         text_color: palette.text,
         border: Border {
             color: palette.primary_bg,
-            width: 1.0,
-            radius: Radius::from(palette.border_radius as u16),
+            width: palette.border_width(ComponentSize::Input),
+            radius: palette.radius(ComponentSize::Input),
         },
         placeholder_color: palette.text,
         handle_color: palette.text,
     };
-    match status {
-        widget::pick_list::Status::Active => style,
+    if palette.high_contrast {
+        style.border.color = palette.border_color_strong;
+    }
+    let state = match status {
+        widget::pick_list::Status::Active => "active",
         widget::pick_list::Status::Hovered => {
             style.background = iced::Background::Color(palette.primary_bg_hover);
-            style
+            "hovered"
         }
         widget::pick_list::Status::Opened { is_hovered: _ } => {
             style.border.color = palette.primary;
-            style
+            if palette.high_contrast {
+                style.border.width = palette.focus_border_width;
+            }
+            "opened"
         }
+    };
+    match palette.picklist.get(state) {
+        Some(style_override) => widget::pick_list::Style {
+            background: style_override.background(style.background),
+            text_color: style_override.text(style.text_color),
+            handle_color: style_override.text(style.handle_color),
+            border: style_override.border(style.border),
+            ..style
+        },
+        None => style,
     }
 }
 
+/// Style of the opened pick list menu.
+///
+/// The menu has no elevation: [`menu::Style`] has no shadow and the menu overlay is created
+/// by the pick list itself, so it can't be wrapped in a container that draws one.
+/// The primary border separates it from the content below instead.
 pub fn menu_style(_: &Theme) -> menu::Style {
     let palette = &OXITHEME;
-    menu::Style {
+    let style = menu::Style {
         background: iced::Background::Color(palette.mantle),
         text_color: palette.text,
         border: Border {
             color: palette.primary,
-            width: 2.0,
-            radius: Radius::from(OXITHEME.border_radius as u16),
+            width: palette.border_width(ComponentSize::Container),
+            radius: palette.radius(ComponentSize::Container),
         },
         selected_text_color: palette.text,
         selected_background: iced::Background::Color(palette.primary_bg_hover),
+    };
+    match palette.picklist.get("menu") {
+        Some(style_override) => menu::Style {
+            background: style_override.background(style.background),
+            text_color: style_override.text(style.text_color),
+            border: style_override.border(style.border),
+            ..style
+        },
+        None => style,
     }
 }
 
@@ -1,20 +1,30 @@
 use iced::{
     Border, Theme,
-    border::Radius,
     widget::{ProgressBar, progress_bar::Style},
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::{
+    theme::theme_impl::{ComponentSize, OXITHEME},
+    widgets::common::{Variant, WithVariant},
+};
+
+pub fn progress_style(theme: &Theme) -> Style {
+    progress_variant_style(Variant::Primary, theme)
+}
 
-pub fn progress_style(_: &Theme) -> Style {
+pub fn progress_variant_style(variant: Variant, _: &Theme) -> Style {
     let palette = &OXITHEME;
     Style {
         background: iced::Background::Color(palette.secondary_bg),
-        bar: iced::Background::Color(palette.primary),
+        bar: iced::Background::Color(palette.variant(variant).base),
         border: Border {
             color: palette.border_color_weak,
-            width: 0.0,
-            radius: Radius::from(palette.border_radius as u16),
+            width: if palette.high_contrast {
+                palette.border_width(ComponentSize::Control)
+            } else {
+                0.0
+            },
+            radius: palette.radius(ComponentSize::Input),
         },
     }
 }
@@ -22,3 +32,9 @@ pub fn progress_style(_: &Theme) -> Style {
 pub fn progress_bar<'a>(range: std::ops::RangeInclusive<f32>, value: f32) -> ProgressBar<'a> {
     iced::widget::progress_bar(range, value).style(progress_style)
 }
+
+impl WithVariant for ProgressBar<'_> {
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme| progress_variant_style(variant, theme))
+    }
+}
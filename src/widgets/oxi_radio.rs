@@ -9,7 +9,6 @@ use iced::advanced::renderer;
 use iced::advanced::text;
 use iced::advanced::widget::tree::{self, Tree};
 use iced::alignment;
-use iced::animation::Easing;
 use iced::mouse;
 use iced::time::Instant;
 use iced::touch;
@@ -18,8 +17,9 @@ use iced::window;
 use iced::{Border, Color, Element, Event, Length, Pixels, Rectangle, Size, Theme};
 use lilt::Animated;
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::theme::theme_impl::{ComponentSize, Motion, OXITHEME};
 use crate::utils::color::darken_color;
+use crate::widgets::common::{Variant, WithVariant};
 
 fn mix(a: Color, b: Color, factor: f32) -> Color {
     let b_amount = factor.clamp(0.0, 1.0);
@@ -215,11 +215,9 @@ where
     fn state(&self) -> tree::State {
         tree::State::new(State {
             now: Instant::now(),
-            transition_selected: Animated::new(self.current == Some(self.value.clone()))
-                .easing(Easing::EaseOut),
-            transition_hovered: Animated::new(false)
-                .easing(Easing::EaseInOut)
-                .duration(400.0),
+            transition_selected: OXITHEME
+                .animated(self.current == Some(self.value.clone()), Motion::Transition),
+            transition_hovered: OXITHEME.animated(false, Motion::Fade),
             text_state: widget::text::State::<Renderer::Paragraph>::default(),
             is_hovered: false,
         })
@@ -580,8 +578,24 @@ impl Catalog for Theme {
     }
 }
 
+impl<V, Message, Renderer> WithVariant for OxiRadio<'_, V, Message, Theme, Renderer>
+where
+    V: PartialEq + Clone,
+    Renderer: text::Renderer,
+{
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme, status| variant_style(variant, theme, status))
+    }
+}
+
 /// The default style of a [`OxiRadio`].
-pub fn default(_: &Theme, status: Status) -> Style {
+pub fn default(theme: &Theme, status: Status) -> Style {
+    variant_style(Variant::Primary, theme, status)
+}
+
+/// Style of a [`OxiRadio`] that is filled with the variant when selected.
+pub fn variant_style(variant: Variant, _: &Theme, status: Status) -> Style {
+    let colors = OXITHEME.selected(variant);
     let background = match status {
         Status::Active {
             is_toggled,
@@ -592,25 +606,17 @@ pub fn default(_: &Theme, status: Status) -> Style {
             translate_animation_progress: animation_progress,
             hover_animation_progress: _,
             hovered: _,
-        } if is_toggled => mix(
-            OXITHEME.primary,
-            OXITHEME.secondary_bg,
-            1.0 - animation_progress,
-        ),
+        } if is_toggled => mix(colors.base, OXITHEME.secondary_bg, 1.0 - animation_progress),
         Status::Active {
             is_toggled: _,
             animation_progress,
-        } => mix(OXITHEME.primary_bg, OXITHEME.primary, animation_progress),
+        } => mix(OXITHEME.primary_bg, colors.base, animation_progress),
         Status::Hovered {
             is_toggled: _,
             translate_animation_progress: _,
             hover_animation_progress,
             hovered: _,
-        } => mix(
-            OXITHEME.secondary_bg,
-            OXITHEME.primary,
-            hover_animation_progress,
-        ),
+        } => mix(OXITHEME.secondary_bg, colors.base, hover_animation_progress),
         Status::Disabled => OXITHEME.mantle,
     };
 
@@ -624,15 +630,11 @@ pub fn default(_: &Theme, status: Status) -> Style {
         | Status::Active {
             is_toggled,
             animation_progress,
-        } if is_toggled => mix(
-            OXITHEME.primary,
-            OXITHEME.secondary_bg,
-            1.0 - animation_progress,
-        ),
+        } if is_toggled => mix(colors.base, OXITHEME.secondary_bg, 1.0 - animation_progress),
         Status::Active {
             is_toggled: _,
             animation_progress,
-        } => mix(OXITHEME.secondary_bg, OXITHEME.primary, animation_progress),
+        } => mix(OXITHEME.secondary_bg, colors.base, animation_progress),
         Status::Hovered {
             is_toggled: _,
             translate_animation_progress: _,
@@ -640,7 +642,7 @@ pub fn default(_: &Theme, status: Status) -> Style {
             hovered: _,
         } => mix(
             OXITHEME.secondary_bg,
-            darken_color(&OXITHEME.primary, 0.25),
+            darken_color(&colors.base, 0.25),
             hover_animation_progress,
         ),
         Status::Disabled => OXITHEME.mantle,
@@ -660,13 +662,20 @@ pub fn default(_: &Theme, status: Status) -> Style {
         Status::Disabled => 0.0,
     };
 
+    let background_border_width = match status {
+        Status::Hovered { hovered: true, .. } if OXITHEME.high_contrast => {
+            OXITHEME.focus_border_width
+        }
+        _ => OXITHEME.border_width(ComponentSize::Control),
+    };
+
     Style {
         background,
         foreground,
         foreground_border_width: 0.0,
         foreground_border_color: Color::TRANSPARENT,
-        background_border_width: 1.0,
-        background_border_color: OXITHEME.primary,
+        background_border_width,
+        background_border_color: colors.base,
         foreground_bounds_horizontal_progress,
     }
 }
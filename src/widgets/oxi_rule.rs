@@ -1,19 +1,25 @@
 use iced::{
     Pixels, Theme,
-    border::Radius,
     widget::{
         Rule,
         rule::{FillMode, Style},
     },
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::{
+    theme::theme_impl::OXITHEME,
+    widgets::common::{Variant, WithVariant},
+};
+
+pub fn rule_style(theme: &Theme) -> Style {
+    rule_variant_style(Variant::Primary, theme)
+}
 
-pub fn rule_style(_: &Theme) -> Style {
+pub fn rule_variant_style(variant: Variant, _: &Theme) -> Style {
     let palette = &OXITHEME;
     Style {
-        color: palette.primary,
-        radius: Radius::from(2.0),
+        color: palette.variant(variant).base,
+        radius: palette.rule_radius.into(),
         fill_mode: FillMode::Percent(90.0),
         snap: false,
     }
@@ -26,3 +32,9 @@ pub fn vertical_rule<'a>(width: impl Into<Pixels>) -> Rule<'a> {
 pub fn horizontal_rule<'a>(width: impl Into<Pixels>) -> Rule<'a> {
     iced::widget::rule::horizontal(width).style(rule_style)
 }
+
+impl WithVariant for Rule<'_> {
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme| rule_variant_style(variant, theme))
+    }
+}
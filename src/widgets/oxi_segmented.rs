@@ -0,0 +1,446 @@
+use iced::advanced::Clipboard;
+use iced::advanced::Layout;
+use iced::advanced::Shell;
+use iced::advanced::Widget;
+use iced::advanced::layout;
+use iced::advanced::renderer;
+use iced::advanced::widget::tree::{self, Tree};
+use iced::keyboard;
+use iced::keyboard::key::Named;
+use iced::mouse;
+use iced::time::Instant;
+use iced::touch;
+use iced::widget::text;
+use iced::window;
+use iced::{
+    Alignment, Border, Color, Element, Event, Length, Padding, Rectangle, Size, Theme, Vector,
+};
+use lilt::Animated;
+
+use crate::theme::theme_impl::{ComponentSize, Elevation, Motion, OXITHEME};
+use crate::utils::color::mix_oklab;
+use crate::widgets::common::{Variant, WithVariant};
+
+/// Row of 2 to 5 joined segments of which exactly one is selected.
+/// Other option counts panic, use a pick list for more.
+///
+/// The indicator slides to the selected segment.
+/// After a click the control is focused and the left and right arrow keys change the selection.
+#[allow(missing_debug_implementations)]
+pub struct SegmentedControl<'a, T, Message>
+where
+    T: ToString + PartialEq + Clone,
+{
+    options: Vec<T>,
+    selected: Option<T>,
+    on_select: Box<dyn Fn(T) -> Message + 'a>,
+    segments: Vec<Element<'a, Message>>,
+    padding: Padding,
+    width: Length,
+    variant: Variant,
+}
+
+/// The state of the [`SegmentedControl`]
+#[derive(Debug)]
+struct State {
+    now: Instant,
+    /// Index of the selected segment
+    indicator: Animated<f32, Instant>,
+    is_focused: bool,
+    hovered: Option<usize>,
+}
+
+impl<'a, T, Message> SegmentedControl<'a, T, Message>
+where
+    T: ToString + PartialEq + Clone,
+    Message: 'a,
+{
+    pub fn new(
+        options: impl Into<Vec<T>>,
+        selected: Option<T>,
+        on_select: impl Fn(T) -> Message + 'a,
+    ) -> Self {
+        let options = options.into();
+        assert!(
+            (2..=5).contains(&options.len()),
+            "segmented controls have 2 to 5 options, use a pick list for more"
+        );
+        let segments = options
+            .iter()
+            .map(|option| {
+                text(option.to_string())
+                    .size(OXITHEME.font_md)
+                    .align_x(Alignment::Center)
+                    .into()
+            })
+            .collect();
+        SegmentedControl {
+            options,
+            selected,
+            on_select: Box::new(on_select),
+            segments,
+            padding: Padding::from([OXITHEME.padding_sm, OXITHEME.padding_lg]),
+            width: Length::Shrink,
+            variant: Variant::Primary,
+        }
+    }
+
+    /// Sets the padding of every segment
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Sets the width, the segments always share it equally
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.options.iter().position(|option| option == selected)
+    }
+
+    fn segment_width(&self, bounds: Rectangle) -> f32 {
+        bounds.width / self.options.len() as f32
+    }
+
+    fn select(&self, index: usize, shell: &mut Shell<'_, Message>) {
+        if Some(index) != self.selected_index() {
+            shell.publish((self.on_select)(self.options[index].clone()));
+        }
+    }
+}
+
+impl<T, Message> WithVariant for SegmentedControl<'_, T, Message>
+where
+    T: ToString + PartialEq + Clone,
+{
+    fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+}
+
+impl<T, Message> Widget<Message, Theme, iced::Renderer> for SegmentedControl<'_, T, Message>
+where
+    T: ToString + PartialEq + Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        let index = self.selected_index().unwrap_or_default();
+        tree::State::new(State {
+            now: Instant::now(),
+            indicator: OXITHEME.animated(index as f32, Motion::Transition),
+            is_focused: false,
+            hovered: None,
+        })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.segments.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.segments);
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, Length::Shrink)
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let count = self.segments.len() as f32;
+        let nodes: Vec<layout::Node> = self
+            .segments
+            .iter_mut()
+            .zip(&mut tree.children)
+            .map(|(segment, tree)| {
+                segment
+                    .as_widget_mut()
+                    .layout(tree, renderer, &limits.loose())
+            })
+            .collect();
+        // every segment is as large as the largest label, so the indicator never changes size
+        let content = nodes.iter().fold(Size::ZERO, |size, node| {
+            Size::new(
+                size.width.max(node.size().width),
+                size.height.max(node.size().height),
+            )
+        });
+        let segment = Size::new(
+            content.width + self.padding.x(),
+            content.height + self.padding.y(),
+        );
+        let size = limits.resolve(
+            self.width,
+            Length::Shrink,
+            Size::new(segment.width * count, segment.height),
+        );
+        let segment = Size::new(size.width / count, segment.height);
+        let children = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| {
+                node.align(Alignment::Center, Alignment::Center, segment)
+                    .translate(Vector::new(segment.width * i as f32, 0.0))
+            })
+            .collect();
+        layout::Node::with_children(Size::new(size.width, segment.height), children)
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &iced::Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let segment_width = self.segment_width(bounds);
+        let count = self.options.len();
+        let selected = self.selected_index();
+        let state = tree.state.downcast_mut::<State>();
+
+        let hovered = cursor
+            .position_over(bounds)
+            .map(|position| (((position.x - bounds.x) / segment_width) as usize).min(count - 1));
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                state.is_focused = hovered.is_some();
+                if let Some(index) = hovered {
+                    self.select(index, shell);
+                    shell.capture_event();
+                }
+                shell.request_redraw();
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                ..
+            }) if state.is_focused => {
+                let index = match (key, selected) {
+                    (Named::ArrowLeft, Some(index)) => index.saturating_sub(1),
+                    (Named::ArrowRight, Some(index)) => (index + 1).min(count - 1),
+                    (Named::ArrowLeft | Named::Home, None) | (Named::Home, _) => 0,
+                    (Named::ArrowRight | Named::End, None) | (Named::End, _) => count - 1,
+                    (Named::Escape | Named::Tab, _) => {
+                        state.is_focused = false;
+                        shell.request_redraw();
+                        return;
+                    }
+                    _ => return,
+                };
+                self.select(index, shell);
+                shell.capture_event();
+            }
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                state.now = *now;
+                if state.indicator.in_progress(*now) {
+                    shell.request_redraw();
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(index) = selected
+            && state.indicator.value != index as f32
+        {
+            state.indicator.transition(index as f32, Instant::now());
+            shell.request_redraw();
+        }
+        if state.hovered != hovered {
+            state.hovered = hovered;
+            shell.request_redraw();
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &iced::Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        use iced::advanced::Renderer as _;
+
+        let palette = &OXITHEME;
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let segment_width = self.segment_width(bounds);
+        let last = (self.options.len() - 1) as f32;
+        let radius = palette.radius(ComponentSize::Input).top_left;
+        let colors = palette.variant(self.variant);
+        let neutral = palette.variant(Variant::Neutral);
+        let segment_bounds = |position: f32| Rectangle {
+            x: bounds.x + position * segment_width,
+            width: segment_width,
+            ..bounds
+        };
+        // only the outer corners are rounded, also while the indicator slides between them
+        let segment_radius = |position: f32| {
+            let start = radius * (1.0 - position).clamp(0.0, 1.0);
+            let end = radius * (position - last + 1.0).clamp(0.0, 1.0);
+            iced::border::Radius::default()
+                .top_left(start)
+                .bottom_left(start)
+                .top_right(end)
+                .bottom_right(end)
+        };
+
+        let (border_width, border_color) = if state.is_focused {
+            (palette.focus_border_width, palette.border_color_strong)
+        } else if palette.high_contrast {
+            (
+                palette.high_contrast_border_width,
+                palette.border_color_strong,
+            )
+        } else {
+            (0.0, Color::TRANSPARENT)
+        };
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: Border {
+                    color: border_color,
+                    width: border_width,
+                    radius: radius.into(),
+                },
+                ..renderer::Quad::default()
+            },
+            neutral.base,
+        );
+
+        // the joints between segments
+        let divider = palette.border_width(ComponentSize::Control);
+        for i in 1..self.options.len() {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle {
+                        x: bounds.x + i as f32 * segment_width - divider / 2.0,
+                        width: divider,
+                        ..bounds
+                    },
+                    ..renderer::Quad::default()
+                },
+                palette.border_color_weak,
+            );
+        }
+
+        let selected = self.selected_index();
+        if let Some(hovered) = state.hovered.filter(|hovered| Some(*hovered) != selected) {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: segment_bounds(hovered as f32),
+                    border: Border {
+                        radius: segment_radius(hovered as f32),
+                        ..Border::default()
+                    },
+                    ..renderer::Quad::default()
+                },
+                neutral.hover,
+            );
+        }
+
+        let position = state.indicator.animate_wrapped(state.now);
+        if selected.is_some() {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: segment_bounds(position),
+                    border: Border {
+                        radius: segment_radius(position),
+                        ..Border::default()
+                    },
+                    shadow: palette.shadow(Elevation::Low),
+                    ..renderer::Quad::default()
+                },
+                colors.base,
+            );
+        }
+
+        for (i, ((segment, tree), layout)) in self
+            .segments
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .enumerate()
+        {
+            // labels change color as the indicator moves over them
+            let coverage = match selected {
+                Some(_) => (1.0 - (position - i as f32).abs()).clamp(0.0, 1.0),
+                None => 0.0,
+            };
+            segment.as_widget().draw(
+                tree,
+                renderer,
+                theme,
+                &renderer::Style {
+                    text_color: mix_oklab(&neutral.contrast, &colors.contrast, coverage),
+                },
+                layout,
+                cursor,
+                viewport,
+            );
+        }
+    }
+}
+
+impl<'a, T, Message> From<SegmentedControl<'a, T, Message>> for Element<'a, Message>
+where
+    T: ToString + PartialEq + Clone + 'a,
+    Message: 'a,
+{
+    fn from(control: SegmentedControl<'a, T, Message>) -> Element<'a, Message> {
+        Element::new(control)
+    }
+}
+
+pub fn segmented_control<'a, T, M>(
+    options: impl Into<Vec<T>>,
+    selected: Option<T>,
+    on_select: impl Fn(T) -> M + 'a,
+) -> SegmentedControl<'a, T, M>
+where
+    T: ToString + PartialEq + Clone,
+    M: 'a,
+{
+    SegmentedControl::new(options, selected, on_select)
+}
+
+#[test]
+#[should_panic(expected = "2 to 5 options")]
+fn test_segmented_control_needs_two_options() {
+    let _ = segmented_control(Vec::<&str>::new(), None, |_| ());
+}
@@ -1,38 +1,59 @@
 use iced::{
     Border, Color, Theme,
-    border::Radius,
     widget::{
         Slider,
         slider::{Handle, HandleShape, Rail, Status, Style},
     },
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::{
+    theme::theme_impl::{ComponentSize, OXITHEME},
+    widgets::common::{Variant, WithVariant},
+};
+
+pub fn slider_style(theme: &Theme, status: Status) -> Style {
+    slider_variant_style(Variant::Primary, theme, status)
+}
 
-pub fn slider_style(_: &Theme, status: Status) -> Style {
+/// The filled part of the rail uses the variant
+pub fn slider_variant_style(variant: Variant, _: &Theme, status: Status) -> Style {
     let palette = &OXITHEME;
-    let style = Style {
+    let mut style = Style {
         rail: Rail {
             backgrounds: (
-                iced::Background::Color(palette.primary),
+                iced::Background::Color(palette.variant(variant).base),
                 iced::Background::Color(palette.secondary_bg),
             ),
-            width: 8.0,
+            width: palette.slider_rail_width,
             border: Border {
                 color: Color::from_rgba(0.0, 0.0, 0.0, 0.0),
                 width: 0.0,
-                radius: Radius::new(palette.border_radius),
+                radius: palette.radius(ComponentSize::Pill),
             },
         },
         handle: Handle {
-            shape: HandleShape::Circle { radius: 8.0 },
-            border_width: 2.0,
+            shape: HandleShape::Circle {
+                radius: palette.slider_handle_radius,
+            },
+            border_width: palette.slider_handle_border_width,
             border_color: palette.text,
             background: iced::Background::Color(palette.text),
         },
     };
+    if palette.high_contrast {
+        style.rail.border.color = palette.border_color_strong;
+        style.rail.border.width = palette.border_width(ComponentSize::Control);
+        style.handle.border_color = palette.border_color_strong;
+    }
     match status {
         Status::Active => style,
+        Status::Hovered | Status::Dragged if palette.high_contrast => Style {
+            handle: Handle {
+                border_width: palette.focus_border_width,
+                ..style.handle
+            },
+            ..style
+        },
         Status::Hovered => style,
         Status::Dragged => style,
     }
@@ -49,3 +70,13 @@ where
 {
     iced::widget::slider(range, value, on_change).style(slider_style)
 }
+
+impl<V, M> WithVariant for Slider<'_, V, M>
+where
+    V: Copy + From<u8> + std::cmp::PartialOrd,
+    M: Clone,
+{
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme, status| slider_variant_style(variant, theme, status))
+    }
+}
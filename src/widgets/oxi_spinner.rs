@@ -0,0 +1,219 @@
+use std::f32::consts::TAU;
+
+use iced::advanced::Clipboard;
+use iced::advanced::Layout;
+use iced::advanced::Shell;
+use iced::advanced::Widget;
+use iced::advanced::layout;
+use iced::advanced::renderer;
+use iced::advanced::widget::tree::{self, Tree};
+use iced::mouse;
+use iced::time::Instant;
+use iced::window;
+use iced::{Border, Color, Element, Event, Length, Point, Rectangle, Size};
+
+use crate::theme::theme_impl::OXITHEME;
+
+/// Number of dots around the spinner
+const DOTS: usize = 8;
+const REVOLUTIONS_PER_SECOND: f32 = 1.0;
+/// Radius of a dot relative to the radius of the spinner
+const DOT_RATIO: f32 = 0.2;
+/// The dots behind the leading one fade out down to this opacity
+const MIN_DOT_ALPHA: f32 = 0.15;
+
+/// Animated loading indicator.
+///
+/// Either has a fixed size, or hides some content and takes over its size,
+/// so a loading button does not change its width.
+#[allow(missing_debug_implementations)]
+pub struct Spinner<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    content: Option<Element<'a, Message, Theme, Renderer>>,
+    size: f32,
+    color: Option<Color>,
+}
+
+/// The state of the [`Spinner`]
+#[derive(Debug)]
+struct State {
+    start: Instant,
+    now: Instant,
+}
+
+impl<'a, Message, Theme, Renderer> Spinner<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    pub const DEFAULT_SIZE: f32 = 20.0;
+
+    pub fn new() -> Self {
+        Spinner {
+            content: None,
+            size: Self::DEFAULT_SIZE,
+            color: None,
+        }
+    }
+
+    /// Spinner that takes the size of the content without drawing it.
+    /// The content does not receive any events.
+    pub fn over(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Spinner {
+            content: Some(content.into()),
+            ..Self::new()
+        }
+    }
+
+    /// Sets the diameter, the spinner is never larger than its bounds
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the color of the dots, defaults to the text color of the parent
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl<Message, Theme, Renderer> Default for Spinner<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Spinner<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        let now = Instant::now();
+        tree::State::new(State { start: now, now })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        match &self.content {
+            Some(content) => tree.diff_children(std::slice::from_ref(content)),
+            None => tree.children.clear(),
+        }
+    }
+
+    fn size(&self) -> Size<Length> {
+        match &self.content {
+            Some(content) => content.as_widget().size(),
+            None => Size::new(Length::Fixed(self.size), Length::Fixed(self.size)),
+        }
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        match &mut self.content {
+            Some(content) => {
+                let node = content
+                    .as_widget_mut()
+                    .layout(&mut tree.children[0], renderer, limits);
+                layout::Node::with_children(node.size(), vec![node])
+            }
+            None => layout::atomic(limits, self.size, self.size),
+        }
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        // with reduced motion the dots stay where they are
+        if OXITHEME.reduced_motion {
+            return;
+        }
+        // only the next frame is requested, and only while the spinner is on screen
+        if let Event::Window(window::Event::RedrawRequested(now)) = event {
+            let state = tree.state.downcast_mut::<State>();
+            state.now = *now;
+            if layout.bounds().intersects(viewport) {
+                shell.request_redraw();
+            }
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let radius = self.size.min(bounds.width).min(bounds.height) / 2.0;
+        let dot_radius = radius * DOT_RATIO;
+        let center = bounds.center();
+        let dot_size = Size::new(dot_radius * 2.0, dot_radius * 2.0);
+        let color = self.color.unwrap_or(style.text_color);
+
+        let elapsed = (state.now - state.start).as_secs_f32();
+        let head = (elapsed * REVOLUTIONS_PER_SECOND).fract() * DOTS as f32;
+
+        for dot in 0..DOTS {
+            let angle = dot as f32 / DOTS as f32 * TAU;
+            // distance behind the leading dot, 0 for the leading dot itself
+            let behind = (head - dot as f32).rem_euclid(DOTS as f32) / DOTS as f32;
+            let alpha = 1.0 - behind * (1.0 - MIN_DOT_ALPHA);
+            let position = Point::new(
+                center.x + (radius - dot_radius) * angle.cos() - dot_radius,
+                center.y + (radius - dot_radius) * angle.sin() - dot_radius,
+            );
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle::new(position, dot_size),
+                    border: Border {
+                        radius: dot_radius.into(),
+                        ..Border::default()
+                    },
+                    ..renderer::Quad::default()
+                },
+                color.scale_alpha(alpha),
+            );
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Spinner<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(
+        spinner: Spinner<'a, Message, Theme, Renderer>,
+    ) -> Element<'a, Message, Theme, Renderer> {
+        Element::new(spinner)
+    }
+}
@@ -1,33 +1,427 @@
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-use iced::Theme;
-use iced::widget::svg::{Status, Style};
+use iced::advanced::Clipboard;
+use iced::advanced::Layout;
+use iced::advanced::Shell;
+use iced::advanced::Widget;
+use iced::advanced::layout;
+use iced::advanced::renderer;
+use iced::advanced::svg::Data;
+use iced::advanced::widget::tree::{self, Tree};
+use iced::mouse;
+use iced::touch;
+use iced::widget::Svg;
+use iced::widget::svg::{Handle, Status, Style};
+use iced::window;
+use iced::{ContentFit, Element, Event, Length, Rectangle, Size, Theme};
+use once_cell::sync::Lazy;
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::{
+    theme::theme_impl::OXITHEME,
+    utils::symbolic::{SvgColoring, SymbolicColors, coloring, recolor},
+    widgets::common::{Variant, VariantColors, WithVariant},
+};
 
-pub enum SvgStyleVariant {
-    Primary,
-    Secondary,
+/// Prepared svgs that are kept, the least recently used one is dropped beyond this
+const PREPARED_CAPACITY: usize = 256;
+
+/// Svgs that went through [`prepare`], by handle id and variant
+static PREPARED: Lazy<Mutex<PreparedCache>> = Lazy::new(|| Mutex::new(PreparedCache::default()));
+
+/// Least recently used cache of [`Prepared`] svgs.
+/// Svg files are only read when they are not cached.
+#[derive(Debug, Default)]
+struct PreparedCache {
+    entries: HashMap<(u64, Variant), (Prepared, u64)>,
+    /// Incremented on every lookup, entries remember when they were last used
+    clock: u64,
+}
+
+impl PreparedCache {
+    fn get(&mut self, variant: Variant, handle: Handle) -> Prepared {
+        self.clock += 1;
+        let key = (handle.id(), variant);
+        if let Some((prepared, last_used)) = self.entries.get_mut(&key) {
+            *last_used = self.clock;
+            return prepared.clone();
+        }
+        if self.entries.len() >= PREPARED_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        let prepared = prepare(variant, handle);
+        self.entries.insert(key, (prepared.clone(), self.clock));
+        prepared
+    }
+}
+
+/// An svg ready to be drawn with theme colors
+#[derive(Debug, Clone)]
+enum Prepared {
+    /// Tinted in the color of the variant for each [`SvgState`]
+    Tinted(Handle),
+    /// Style classes recolored, once for each [`SvgState`]
+    Recolored {
+        idle: Handle,
+        hovered: Handle,
+        pressed: Handle,
+    },
+    /// Colors are part of the svg itself
+    Untinted(Handle),
+}
+
+#[deprecated(note = "use `Variant` instead")]
+pub type SvgStyleVariant = Variant;
+
+/// Cursor interaction of a [`ThemedSvg`], unlike [`Status`] it knows about presses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SvgState {
+    #[default]
+    Idle,
+    Hovered,
+    Pressed,
+}
+
+impl SvgState {
+    fn color(self, colors: &VariantColors) -> iced::Color {
+        match self {
+            SvgState::Idle => colors.base,
+            SvgState::Hovered => colors.hover,
+            SvgState::Pressed => colors.active,
+        }
+    }
+}
+
+/// Tints single color svgs in the variant, hovered svgs use its hover color.
+/// [`ThemedSvg`] also uses the active color while it is pressed.
+pub fn svg_style(variant: Variant, _: &Theme, status: Status) -> Style {
+    let state = match status {
+        Status::Hovered => SvgState::Hovered,
+        Status::Idle => SvgState::Idle,
+    };
+    Style {
+        color: Some(state.color(&OXITHEME.variant(variant))),
+    }
+}
+
+fn symbolic_colors(variant: Variant, state: SvgState) -> SymbolicColors {
+    SymbolicColors {
+        foreground: state.color(&OXITHEME.variant(variant)),
+        success: OXITHEME.variant(Variant::Success).base,
+        warning: OXITHEME.variant(Variant::Warning).base,
+        error: OXITHEME.variant(Variant::Danger).base,
+    }
+}
+
+/// Reads the svg and substitutes theme colors where the icon asks for them.
+/// Svgs that can't be read are tinted as before, compressed ones are left alone.
+fn prepare(variant: Variant, handle: Handle) -> Prepared {
+    let (bytes, symbolic_name) = match handle.data() {
+        Data::Path(path) => match std::fs::read(path) {
+            Ok(bytes) => (Cow::Owned(bytes), is_symbolic_path(path)),
+            Err(_) => return Prepared::Tinted(handle),
+        },
+        Data::Bytes(bytes) => (Cow::Borrowed(bytes.as_ref()), false),
+    };
+    // svgz is gzip compressed, which also fails to decode as utf-8
+    let Ok(svg) = std::str::from_utf8(&bytes) else {
+        return Prepared::Untinted(handle);
+    };
+    let recolored =
+        |state| Handle::from_memory(recolor(svg, &symbolic_colors(variant, state)).into_bytes());
+    match coloring(svg, symbolic_name) {
+        SvgColoring::Monochrome => Prepared::Tinted(handle),
+        SvgColoring::Classes => Prepared::Recolored {
+            idle: recolored(SvgState::Idle),
+            hovered: recolored(SvgState::Hovered),
+            pressed: recolored(SvgState::Pressed),
+        },
+        SvgColoring::FullColor => Prepared::Untinted(handle),
+    }
+}
+
+fn is_symbolic_path(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem.ends_with("-symbolic"))
+}
+
+pub fn svg_from_path<'a>(variant: Variant, svg_path: impl Into<PathBuf>) -> ThemedSvg<'a> {
+    svg_from_handle(variant, Handle::from_path(svg_path))
+}
+
+/// Svg in theme colors: single color icons are tinted in the variant,
+/// symbolic icons with style classes are recolored and full color icons are left untouched.
+///
+/// Files are read once and kept until they are the least recently used of
+/// [`PREPARED_CAPACITY`] svgs.
+pub fn svg_from_handle<'a>(variant: Variant, handle: Handle) -> ThemedSvg<'a> {
+    let prepared = PREPARED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(variant, handle);
+    ThemedSvg::new(variant, prepared)
+}
+
+impl WithVariant for iced::widget::Svg<'_> {
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme, status| svg_style(variant, theme, status))
+    }
+}
+
+/// Svg in theme colors that follows the cursor: hovered it uses the hover color of its
+/// variant and pressed the active color, see [`svg_from_handle`].
+///
+/// Presses are not captured, so the svg can be the content of a button.
+#[allow(missing_debug_implementations)]
+pub struct ThemedSvg<'a> {
+    idle: Svg<'a>,
+    hovered: Svg<'a>,
+    pressed: Svg<'a>,
+}
+
+/// The state of the [`ThemedSvg`]
+#[derive(Debug, Default)]
+struct State {
+    is_pressed: bool,
+    /// State of the last drawn frame
+    drawn: SvgState,
+}
+
+impl<'a> ThemedSvg<'a> {
+    fn new(variant: Variant, prepared: Prepared) -> Self {
+        match prepared {
+            Prepared::Tinted(handle) => {
+                let tinted = |state: SvgState| {
+                    iced::widget::svg(handle.clone()).style(move |_, _| Style {
+                        color: Some(state.color(&OXITHEME.variant(variant))),
+                    })
+                };
+                ThemedSvg {
+                    idle: tinted(SvgState::Idle),
+                    hovered: tinted(SvgState::Hovered),
+                    pressed: tinted(SvgState::Pressed),
+                }
+            }
+            Prepared::Recolored {
+                idle,
+                hovered,
+                pressed,
+            } => ThemedSvg {
+                idle: iced::widget::svg(idle),
+                hovered: iced::widget::svg(hovered),
+                pressed: iced::widget::svg(pressed),
+            },
+            Prepared::Untinted(handle) => ThemedSvg {
+                idle: iced::widget::svg(handle.clone()),
+                hovered: iced::widget::svg(handle.clone()),
+                pressed: iced::widget::svg(handle),
+            },
+        }
+    }
+
+    fn map(self, f: impl Fn(Svg<'a>) -> Svg<'a>) -> Self {
+        ThemedSvg {
+            idle: f(self.idle),
+            hovered: f(self.hovered),
+            pressed: f(self.pressed),
+        }
+    }
+
+    pub fn width(self, width: impl Into<Length>) -> Self {
+        let width = width.into();
+        self.map(|svg| svg.width(width))
+    }
+
+    pub fn height(self, height: impl Into<Length>) -> Self {
+        let height = height.into();
+        self.map(|svg| svg.height(height))
+    }
+
+    pub fn content_fit(self, content_fit: ContentFit) -> Self {
+        self.map(|svg| svg.content_fit(content_fit))
+    }
+
+    pub fn opacity(self, opacity: f32) -> Self {
+        self.map(|svg| svg.opacity(opacity))
+    }
+
+    /// Replaces the theme colors, hovered and pressed svgs get [`Status::Hovered`]
+    pub fn style(self, style: impl Fn(&Theme, Status) -> Style + Clone + 'a) -> Self {
+        let hovered = style.clone();
+        let pressed = style.clone();
+        ThemedSvg {
+            idle: self.idle.style(move |theme, _| style(theme, Status::Idle)),
+            hovered: self
+                .hovered
+                .style(move |theme, _| hovered(theme, Status::Hovered)),
+            pressed: self
+                .pressed
+                .style(move |theme, _| pressed(theme, Status::Hovered)),
+        }
+    }
+
+    fn svg(&self, state: SvgState) -> &Svg<'a> {
+        match state {
+            SvgState::Idle => &self.idle,
+            SvgState::Hovered => &self.hovered,
+            SvgState::Pressed => &self.pressed,
+        }
+    }
 }
 
-pub fn svg_style(variant: &SvgStyleVariant, _: &Theme, status: Status) -> Style {
-    let palette = &OXITHEME;
-    let color = match variant {
-        SvgStyleVariant::Primary => palette.primary,
-        SvgStyleVariant::Secondary => palette.secondary,
+fn current_state(state: &State, layout: Layout<'_>, cursor: mouse::Cursor) -> SvgState {
+    match (cursor.is_over(layout.bounds()), state.is_pressed) {
+        (true, true) => SvgState::Pressed,
+        (true, false) => SvgState::Hovered,
+        (false, _) => SvgState::Idle,
+    }
+}
+
+impl<Message> Widget<Message, Theme, iced::Renderer> for ThemedSvg<'_> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Widget::<Message, Theme, iced::Renderer>::size(&self.idle)
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        Widget::<Message, Theme, iced::Renderer>::layout(
+            &mut self.idle,
+            &mut Tree::empty(),
+            renderer,
+            limits,
+        )
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &iced::Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. })
+                if cursor.is_over(layout.bounds()) =>
+            {
+                state.is_pressed = true;
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. } | touch::Event::FingerLost { .. }) => {
+                state.is_pressed = false;
+            }
+            _ => {}
+        }
+        let current = current_state(state, layout, cursor);
+        if let Event::Window(window::Event::RedrawRequested(_)) = event {
+            state.drawn = current;
+        } else if state.drawn != current {
+            shell.request_redraw();
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = current_state(tree.state.downcast_ref::<State>(), layout, cursor);
+        Widget::<Message, Theme, iced::Renderer>::draw(
+            self.svg(state),
+            &Tree::empty(),
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+}
+
+impl<'a, Message> From<ThemedSvg<'a>> for Element<'a, Message> {
+    fn from(svg: ThemedSvg<'a>) -> Element<'a, Message> {
+        Element::new(svg)
+    }
+}
+
+#[test]
+fn test_compressed_svgs_are_left_alone() {
+    let path = std::env::temp_dir().join("oxiced-test-icon.svgz");
+    // gzip header
+    std::fs::write(&path, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+    let prepared = prepare(Variant::Primary, Handle::from_path(&path));
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(prepared, Prepared::Untinted(_)));
+
+    let monochrome = Handle::from_memory(r#"<svg><path fill="currentColor"/></svg>"#.as_bytes());
+    assert!(matches!(
+        prepare(Variant::Primary, monochrome),
+        Prepared::Tinted(_)
+    ));
+}
+
+#[test]
+fn test_prepared_svgs_are_bounded() {
+    let mut cache = PreparedCache::default();
+    let svg = |index: usize| {
+        Handle::from_memory(
+            format!(r#"<svg><path id="{index}" fill="currentColor"/></svg>"#).into_bytes(),
+        )
     };
-    let style = Style { color: Some(color) };
-    match status {
-        // TODO differentiate perhaps?
-        Status::Hovered => style,
-        Status::Idle => style,
+    let kept = svg(0);
+    for index in 0..PREPARED_CAPACITY * 2 {
+        cache.get(Variant::Primary, svg(index));
+        // the first svg stays because it keeps being used
+        cache.get(Variant::Primary, kept.clone());
     }
+    assert_eq!(cache.entries.len(), PREPARED_CAPACITY);
+    assert!(cache.entries.contains_key(&(kept.id(), Variant::Primary)));
 }
 
-pub fn svg_from_path<'a>(
-    variant: SvgStyleVariant,
-    svg_path: impl Into<PathBuf>,
-) -> iced::widget::Svg<'a> {
-    let handle = iced::widget::svg::Handle::from_path(svg_path);
-    iced::widget::svg(handle).style(move |theme, status| svg_style(&variant, theme, status))
+#[test]
+fn test_recolored_svgs_follow_the_state() {
+    let svg = r#"<svg><path class="error" fill="red"/><path fill="currentColor"/></svg>"#;
+    let Prepared::Recolored {
+        idle,
+        hovered,
+        pressed,
+    } = prepare(Variant::Primary, Handle::from_memory(svg.as_bytes()))
+    else {
+        panic!("svgs with style classes are recolored");
+    };
+    assert_ne!(idle.id(), hovered.id());
+    assert_ne!(hovered.id(), pressed.id());
 }
@@ -1,10 +1,9 @@
 use iced::{
     Border, Theme,
-    border::Radius,
     widget::text_input::{Status, Style},
 };
 
-use crate::theme::theme_impl::OXITHEME;
+use crate::theme::theme_impl::{ComponentSize, OXITHEME};
 
 pub fn text_input_style(_: &Theme, status: Status) -> Style {
     let palette = &OXITHEME;
@@ -12,30 +11,45 @@ pub fn text_input_style(_: &Theme, status: Status) -> Style {
         background: iced::Background::Color(palette.mantle),
         border: Border {
             color: palette.secondary_bg,
-            width: 1.0,
-            radius: Radius::from(palette.border_radius as u16),
+            width: palette.border_width(ComponentSize::Input),
+            radius: palette.radius(ComponentSize::Input),
         },
         icon: palette.text,
         placeholder: palette.text_muted,
         value: palette.text,
         selection: palette.primary,
     };
-    match status {
-        Status::Active => style,
+    if palette.high_contrast {
+        style.border.color = palette.border_color_strong;
+    }
+    let state = match status {
+        Status::Active => "active",
         Status::Hovered => {
             style.background = iced::Background::Color(palette.mantle_hover);
             style.border.color = palette.primary;
-            style
+            "hovered"
         }
         Status::Focused { is_hovered: _ } => {
             style.background = iced::Background::Color(palette.mantle);
             style.border.color = palette.primary;
-            style
+            if palette.high_contrast {
+                style.border.width = palette.focus_border_width;
+            }
+            "focused"
         }
         Status::Disabled => {
             style.value = palette.text_muted;
-            style
+            "disabled"
         }
+    };
+    match palette.text_input.get(state) {
+        Some(style_override) => Style {
+            background: style_override.background(style.background),
+            value: style_override.text(style.value),
+            border: style_override.border(style.border),
+            ..style
+        },
+        None => style,
     }
 }
 
@@ -40,7 +40,6 @@ use iced::advanced::renderer;
 use iced::advanced::text;
 use iced::advanced::widget::tree::{self, Tree};
 use iced::alignment;
-use iced::animation::Easing;
 use iced::mouse;
 use iced::time::Instant;
 use iced::touch;
@@ -49,6 +48,11 @@ use iced::window;
 use iced::{Border, Color, Element, Event, Length, Pixels, Rectangle, Size, Theme};
 use lilt::Animated;
 
+use crate::{
+    theme::theme_impl::{Motion, OXITHEME},
+    widgets::common::{Variant, WithVariant},
+};
+
 fn mix(a: Color, b: Color, factor: f32) -> Color {
     let b_amount = factor.clamp(0.0, 1.0);
     let a_amount = 1.0 - b_amount;
@@ -288,7 +292,7 @@ where
     fn state(&self) -> tree::State {
         tree::State::new(State {
             now: Instant::now(),
-            transition: Animated::new(self.is_toggled).easing(Easing::EaseOut),
+            transition: OXITHEME.animated(self.is_toggled, Motion::Transition),
             text_state: widget::text::State::<Renderer::Paragraph>::default(),
         })
     }
@@ -608,9 +612,24 @@ impl Catalog for Theme {
     }
 }
 
+impl<Message, Renderer> WithVariant for OxiToggler<'_, Message, Theme, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    fn variant(self, variant: Variant) -> Self {
+        self.style(move |theme, status| variant_style(variant, theme, status))
+    }
+}
+
 /// The default style of a [`OxiToggler`].
 pub fn default(theme: &Theme, status: Status) -> Style {
+    variant_style(Variant::Primary, theme, status)
+}
+
+/// Style of a [`OxiToggler`] that is filled with the variant when toggled.
+pub fn variant_style(variant: Variant, theme: &Theme, status: Status) -> Style {
     let palette = theme.extended_palette();
+    let colors = OXITHEME.selected(variant);
 
     let background = match status {
         Status::Active {
@@ -623,14 +642,14 @@ pub fn default(theme: &Theme, status: Status) -> Style {
         } => {
             if is_toggled {
                 mix(
-                    palette.primary.strong.color,
+                    colors.base,
                     palette.background.strong.color,
                     1.0 - animation_progress,
                 )
             } else {
                 mix(
                     palette.background.strong.color,
-                    palette.primary.strong.color,
+                    colors.base,
                     animation_progress,
                 )
             }
@@ -644,7 +663,7 @@ pub fn default(theme: &Theme, status: Status) -> Style {
             animation_progress: _,
         } => {
             if is_toggled {
-                palette.primary.strong.text
+                colors.contrast
             } else {
                 palette.background.base.color
             }
@@ -653,10 +672,12 @@ pub fn default(theme: &Theme, status: Status) -> Style {
             is_toggled,
             animation_progress: _,
         } => {
-            if is_toggled {
+            if is_toggled && OXITHEME.high_contrast {
+                colors.contrast
+            } else if is_toggled {
                 Color {
                     a: 0.5,
-                    ..palette.primary.strong.text
+                    ..colors.contrast
                 }
             } else {
                 palette.background.weak.color
@@ -677,13 +698,24 @@ pub fn default(theme: &Theme, status: Status) -> Style {
         Status::Disabled => 0.0,
     };
 
+    let (background_border_width, background_border_color) = match status {
+        Status::Hovered { .. } if OXITHEME.high_contrast => {
+            (OXITHEME.focus_border_width, OXITHEME.border_color_strong)
+        }
+        _ if OXITHEME.high_contrast => (
+            OXITHEME.high_contrast_border_width,
+            OXITHEME.border_color_strong,
+        ),
+        _ => (0.0, Color::TRANSPARENT),
+    };
+
     Style {
         background,
         foreground,
         foreground_border_width: 0.0,
         foreground_border_color: Color::TRANSPARENT,
-        background_border_width: 0.0,
-        background_border_color: Color::TRANSPARENT,
+        background_border_width,
+        background_border_color,
         foreground_bounds_horizontal_progress,
     }
 }